@@ -0,0 +1,238 @@
+//! Simulated Diffie-Hellman echo protocol with an active network attacker (Challenges 34 and 35).
+//!
+//! Alice and Bob run on their own threads and only talk through an in-process network,
+//! which hands every message to a [`Mallory`] before delivering it.
+//! The protocol is:
+//!
+//! 1. A -> B: [`Message::Negotiate`] with the group `(p, g)` Alice wants to use
+//! 2. B -> A: [`Message::Accept`] with the group Bob agreed to, which Alice adopts
+//! 3. A -> B: [`Message::PublicKey`] `A`
+//! 4. B -> A: [`Message::PublicKey`] `B`
+//! 5. A -> B: [`Message::Encrypted`] message under `SHA1(s)[0..16]` with a random IV
+//! 6. B -> A: [`Message::Encrypted`] echo of the decrypted message, under a fresh IV
+//!
+//! Neither party validates the group or the public values, which is what the attacks exploit.
+
+use std::sync::Mutex;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use cryptopals_bigint::BigUint;
+use cryptopals_bigint::dh::{Group, Kdf};
+use cryptopals_modes::cbc::Cbc;
+use cryptopals_padding::pkcs7::Pkcs7;
+use cryptopals_primitives::{BlockCipher, aes::Aes128};
+use hybrid_array::sizes::U16;
+use rand::prelude::*;
+use thiserror::Error;
+
+/// Message exchanged between Alice and Bob.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    /// Group proposed by Alice.
+    Negotiate(Group),
+    /// Group acknowledged by Bob.
+    Accept(Group),
+    /// Public Diffie-Hellman value of the sender.
+    PublicKey(BigUint),
+    /// AES-128-CBC encrypted and PKCS#7 padded payload.
+    Encrypted { iv: [u8; 16], ciphertext: Vec<u8> },
+}
+
+impl Message {
+    /// Encrypts `plaintext` under `key` with a random IV.
+    pub fn encrypt(key: &[u8; 16], plaintext: &[u8]) -> Self {
+        let mut iv = [0; 16];
+        rand::rng().fill_bytes(&mut iv);
+        let mut cbc = Cbc::new(Aes128::new((*key).into()), iv.into());
+        let mut ciphertext = vec![0; (plaintext.len() + 1).next_multiple_of(16)];
+        ciphertext[..plaintext.len()].copy_from_slice(plaintext);
+        cbc.encrypt_padded::<Pkcs7<U16>>(&mut ciphertext, plaintext.len());
+        Self::Encrypted { iv, ciphertext }
+    }
+
+    /// Decrypts an [`Message::Encrypted`] payload under `key`.
+    ///
+    /// Returns an error for any other kind of message, or if the padding is invalid.
+    pub fn decrypt(&self, key: &[u8; 16]) -> Result<Vec<u8>, ProtocolError> {
+        let Self::Encrypted { iv, ciphertext } = self else {
+            return Err(ProtocolError::UnexpectedMessage(self.clone()));
+        };
+        let mut buffer = ciphertext.clone();
+        let mut cbc = Cbc::new(Aes128::new((*key).into()), (*iv).into());
+        Ok(cbc.decrypt_padded::<Pkcs7<U16>>(&mut buffer)?.to_vec())
+    }
+}
+
+/// Errors that can make either party abort the protocol.
+#[derive(Debug, Error)]
+pub enum ProtocolError {
+    #[error("the other party disconnected")]
+    Disconnected,
+    #[error("unexpected message: {0:?}")]
+    UnexpectedMessage(Message),
+    #[error("decryption failed: {0}")]
+    Decryption(#[from] cryptopals_modes::Error),
+}
+
+/// Direction a message travels in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Direction {
+    AliceToBob,
+    BobToAlice,
+}
+
+/// Attacker sitting on the network between Alice and Bob.
+pub trait Mallory {
+    /// Inspects a message in transit and returns the message to deliver instead.
+    fn intercept(&mut self, direction: Direction, message: Message) -> Message;
+}
+
+/// Network without an attacker, which delivers every message unchanged.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct HonestNetwork;
+
+impl Mallory for HonestNetwork {
+    fn intercept(&mut self, _direction: Direction, message: Message) -> Message {
+        message
+    }
+}
+
+/// Endpoint of one party on the simulated network.
+struct Endpoint {
+    outgoing: Sender<Message>,
+    incoming: Receiver<Message>,
+}
+
+impl Endpoint {
+    fn send(&self, message: Message) -> Result<(), ProtocolError> {
+        self.outgoing
+            .send(message)
+            .map_err(|_| ProtocolError::Disconnected)
+    }
+
+    fn receive(&self) -> Result<Message, ProtocolError> {
+        self.incoming
+            .recv()
+            .map_err(|_| ProtocolError::Disconnected)
+    }
+}
+
+/// Runs the protocol between Alice, proposing `group`, and Bob, with `mallory` relaying all messages.
+///
+/// Alice sends `plaintext` and returns the echo she receives from Bob.
+pub fn run_protocol(
+    group: &Group,
+    plaintext: &[u8],
+    mallory: &mut (impl Mallory + Send),
+) -> Result<Vec<u8>, ProtocolError> {
+    let (alice_out_tx, alice_out_rx) = mpsc::channel();
+    let (alice_in_tx, alice_in_rx) = mpsc::channel();
+    let (bob_out_tx, bob_out_rx) = mpsc::channel();
+    let (bob_in_tx, bob_in_rx) = mpsc::channel();
+    let alice_endpoint = Endpoint {
+        outgoing: alice_out_tx,
+        incoming: alice_in_rx,
+    };
+    let bob_endpoint = Endpoint {
+        outgoing: bob_out_tx,
+        incoming: bob_in_rx,
+    };
+    let mallory = Mutex::new(mallory);
+
+    thread::scope(|s| {
+        let alice = s.spawn(move || alice(alice_endpoint, group, plaintext));
+        let bob = s.spawn(move || bob(bob_endpoint));
+        // one relay per direction, so a party hanging up disconnects the other one
+        s.spawn(|| relay(Direction::AliceToBob, alice_out_rx, bob_in_tx, &mallory));
+        s.spawn(|| relay(Direction::BobToAlice, bob_out_rx, alice_in_tx, &mallory));
+
+        let echo = alice.join().expect("Alice panicked");
+        match (echo, bob.join().expect("Bob panicked")) {
+            // report why Bob hung up, rather than just that he did
+            (Err(ProtocolError::Disconnected), Err(err)) => Err(err),
+            (echo, _) => echo,
+        }
+    })
+}
+
+/// Passes all messages from `incoming` through `mallory` and on to `outgoing`.
+fn relay<M: Mallory>(
+    direction: Direction,
+    incoming: Receiver<Message>,
+    outgoing: Sender<Message>,
+    mallory: &Mutex<&mut M>,
+) {
+    for message in incoming {
+        let message = mallory.lock().unwrap().intercept(direction, message);
+        if outgoing.send(message).is_err() {
+            break;
+        }
+    }
+}
+
+/// Alice's side of the protocol.
+fn alice(endpoint: Endpoint, group: &Group, plaintext: &[u8]) -> Result<Vec<u8>, ProtocolError> {
+    endpoint.send(Message::Negotiate(group.clone()))?;
+    let group = match endpoint.receive()? {
+        Message::Accept(group) => group,
+        message => return Err(ProtocolError::UnexpectedMessage(message)),
+    };
+
+    let key_pair = group.generate_key_pair(&mut rand::rng());
+    endpoint.send(Message::PublicKey(key_pair.public().clone()))?;
+    let key = match endpoint.receive()? {
+        Message::PublicKey(b) => Kdf::Sha1.derive_key(&key_pair.shared_secret(&b)),
+        message => return Err(ProtocolError::UnexpectedMessage(message)),
+    };
+
+    endpoint.send(Message::encrypt(&key, plaintext))?;
+    endpoint.receive()?.decrypt(&key)
+}
+
+/// Bob's side of the protocol.
+fn bob(endpoint: Endpoint) -> Result<(), ProtocolError> {
+    let group = match endpoint.receive()? {
+        Message::Negotiate(group) => group,
+        message => return Err(ProtocolError::UnexpectedMessage(message)),
+    };
+    endpoint.send(Message::Accept(group.clone()))?;
+
+    let key_pair = group.generate_key_pair(&mut rand::rng());
+    let key = match endpoint.receive()? {
+        Message::PublicKey(a) => Kdf::Sha1.derive_key(&key_pair.shared_secret(&a)),
+        message => return Err(ProtocolError::UnexpectedMessage(message)),
+    };
+    endpoint.send(Message::PublicKey(key_pair.public().clone()))?;
+
+    let plaintext = endpoint.receive()?.decrypt(&key)?;
+    endpoint.send(Message::encrypt(&key, &plaintext))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn honest_echo() {
+        let message = b"YELLOW SUBMARINE";
+        let echo = run_protocol(&Group::modp_1536(), message, &mut HonestNetwork).unwrap();
+        assert_eq!(echo, message);
+    }
+
+    #[test]
+    fn substituted_keys_break_echo() {
+        struct KeySubstitution;
+        impl Mallory for KeySubstitution {
+            fn intercept(&mut self, _direction: Direction, message: Message) -> Message {
+                match message {
+                    Message::PublicKey(_) => Message::PublicKey(BigUint::from(2u64)),
+                    message => message,
+                }
+            }
+        }
+        // both sides derive different keys, so the echo cannot survive the round trip
+        let echo = run_protocol(&Group::modp_1536(), b"hello", &mut KeySubstitution);
+        assert_ne!(echo.ok().as_deref(), Some(&b"hello"[..]));
+    }
+}
@@ -0,0 +1,184 @@
+//! HMAC-SHA1 web service with an artificial timing leak (Challenges 31 and 32).
+//!
+//! The server listens on the loopback interface only and answers `GET /test?file=..&signature=..`
+//! with `200 OK` if `signature` is the hex-encoded HMAC of `file`, and with `500` otherwise.
+//! Signatures are checked with [`insecure_compare`], sleeping after every matching byte.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use cryptopals_primitives::hmac::{Hmac, insecure_compare};
+use cryptopals_primitives::sha1::Sha1Core;
+use cryptopals_utils::hex;
+use rand::prelude::*;
+
+/// Loopback HTTP server validating HMAC-SHA1 signatures with an early-exit comparison.
+///
+/// The server runs on a background thread until it is dropped.
+pub struct HmacTimingServer {
+    addr: SocketAddr,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl HmacTimingServer {
+    /// Starts a new server with a random key on an ephemeral loopback port.
+    ///
+    /// `delay` is how long the comparison sleeps after each matching byte.
+    pub fn start(delay: Duration) -> io::Result<Self> {
+        let mut key = [0; 32];
+        rand::rng().fill_bytes(&mut key);
+        Self::with_key(&key, delay)
+    }
+
+    /// Starts a new server with the given `key` on an ephemeral loopback port.
+    pub fn with_key(key: &[u8], delay: Duration) -> io::Result<Self> {
+        let key = key.to_vec();
+        let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, 0))?;
+        let addr = listener.local_addr()?;
+        let shutdown = Arc::new(AtomicBool::new(false));
+
+        let shutdown_flag = Arc::clone(&shutdown);
+        let handle = thread::spawn(move || {
+            for stream in listener.incoming() {
+                if shutdown_flag.load(Ordering::Relaxed) {
+                    break;
+                }
+                // errors only affect a single request, so keep serving
+                if let Ok(stream) = stream {
+                    let _ = handle_request(stream, &key, delay);
+                }
+            }
+        });
+
+        Ok(Self {
+            addr,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    /// Returns the address the server is listening on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+}
+
+impl Drop for HmacTimingServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        // wake up the blocking accept, so the thread sees the flag
+        let _ = TcpStream::connect(self.addr);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// Answers a single request on `stream`.
+fn handle_request(stream: TcpStream, key: &[u8], delay: Duration) -> io::Result<()> {
+    stream.set_nodelay(true)?;
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    let valid = match request_line.split_whitespace().collect::<Vec<_>>()[..] {
+        ["GET", target, _] => match parse_target(target) {
+            Some((file, signature)) => {
+                let mac = Hmac::<Sha1Core>::mac(key, file.as_bytes());
+                insecure_compare(&mac, &signature, || thread::sleep(delay))
+            }
+            None => false,
+        },
+        _ => false,
+    };
+
+    let status = if valid {
+        "200 OK"
+    } else {
+        "500 Internal Server Error"
+    };
+    // single write, so Nagle's algorithm cannot delay part of the response
+    let response = format!("HTTP/1.1 {status}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n");
+    (&stream).write_all(response.as_bytes())
+}
+
+/// Extracts the file name and the decoded signature from a `/test?file=..&signature=..` target.
+///
+/// Returns `None` for any other path, missing parameters, or a signature that is not valid hex.
+fn parse_target(target: &str) -> Option<(&str, Vec<u8>)> {
+    let query = target.strip_prefix("/test?")?;
+    let (mut file, mut signature) = (None, None);
+    for pair in query.split('&') {
+        match pair.split_once('=')? {
+            ("file", value) => file = Some(value),
+            ("signature", value) => signature = Some(value),
+            _ => {}
+        }
+    }
+
+    let signature = signature?;
+    if signature.len() % 2 != 0 || !signature.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return None;
+    }
+    Some((file?, hex::decode(signature)))
+}
+
+/// Sends a single request to the server at `addr`.
+///
+/// Returns whether the server accepted `signature` as the HMAC of `file`.
+pub fn request(addr: SocketAddr, file: &str, signature: &[u8]) -> io::Result<bool> {
+    let mut stream = TcpStream::connect(addr)?;
+    stream.set_nodelay(true)?;
+    let request = format!(
+        "GET /test?file={}&signature={} HTTP/1.1\r\nHost: localhost\r\n\r\n",
+        file,
+        hex::encode(signature)
+    );
+    stream.write_all(request.as_bytes())?;
+
+    let mut status_line = String::new();
+    BufReader::new(stream).read_line(&mut status_line)?;
+    Ok(status_line.split_whitespace().nth(1) == Some("200"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_signature() {
+        let server = HmacTimingServer::with_key(b"key", Duration::ZERO).unwrap();
+        let mac = Hmac::<Sha1Core>::mac(b"key", b"foo");
+        assert!(request(server.addr(), "foo", &mac).unwrap());
+        assert!(!request(server.addr(), "bar", &mac).unwrap());
+        assert!(!request(server.addr(), "foo", &[0; 20]).unwrap());
+        assert!(!request(server.addr(), "foo", &[]).unwrap());
+    }
+
+    #[test]
+    fn survives_malformed_requests() {
+        let server = HmacTimingServer::with_key(b"key", Duration::ZERO).unwrap();
+        for target in [
+            "/test?file=foo&signature=zz",
+            "/test?file=foo&signature=abc",
+            "/test?file=foo&signature",
+            "/test?file=foo",
+            "/other?file=foo",
+        ] {
+            let mut stream = TcpStream::connect(server.addr()).unwrap();
+            write!(stream, "GET {target} HTTP/1.1\r\n\r\n").unwrap();
+            let mut status_line = String::new();
+            BufReader::new(stream).read_line(&mut status_line).unwrap();
+            assert_eq!(status_line.split_whitespace().nth(1), Some("500"));
+        }
+
+        // the server is still up afterwards
+        let mac = Hmac::<Sha1Core>::mac(b"key", b"foo");
+        assert!(request(server.addr(), "foo", &mac).unwrap());
+    }
+}
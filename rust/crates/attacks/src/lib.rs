@@ -1,12 +1,21 @@
 use std::collections::HashMap;
+use std::marker::PhantomData;
 
-use cryptopals_modes::{cbc::Cbc, ecb::Ecb};
+use cryptopals_modes::{cbc::Cbc, ctr::Ctr, ecb::Ecb};
 use cryptopals_padding::pkcs7::Pkcs7;
+use cryptopals_primitives::merkle_damgard::{Hasher, MerkleDamgard};
 use cryptopals_primitives::{BlockCipher, aes::Aes128};
 use cryptopals_utils::url_params::{build_url_params, parse_url_params};
 use cryptopals_utils::{base64, hex};
+use hybrid_array::Array;
 use hybrid_array::sizes::U16;
 use rand::prelude::*;
+use thiserror::Error;
+
+pub mod dh_protocol;
+pub mod hmac_timing;
+pub mod mt19937;
+pub mod srp;
 
 /// Indicates which block cipher mode was used.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -43,7 +52,7 @@ pub fn encryption_oracle_ecb_cbc(input: &[u8]) -> (Vec<u8>, ModeUsed) {
     // randomly encrypt with either ECB or CBC (50/50)
     if rng.random_bool(0.5) {
         let mut ecb = Ecb::new(aes);
-        ecb.encrypt_padded::<Pkcs7<U16>>(&mut buffer, len);
+        ecb.encrypt_padded::<Pkcs7<U16>>(&mut buffer, len).unwrap();
         (buffer, ModeUsed::ECB)
     } else {
         let mut cbc = Cbc::new(aes, [0; 16].into());
@@ -94,7 +103,7 @@ impl ByteAtATimeEcbOracle {
 
         // encrypt with ECB
         let mut ecb = Ecb::new(self.aes.clone());
-        ecb.encrypt_padded::<Pkcs7<U16>>(&mut buffer, len);
+        ecb.encrypt_padded::<Pkcs7<U16>>(&mut buffer, len).unwrap();
         buffer
     }
 
@@ -151,7 +160,8 @@ impl EcbUserOracle {
         let mut ecb = Ecb::new(self.aes.clone());
         let mut buffer = vec![0; raw_token.len().next_multiple_of(16)];
         buffer[..raw_token.len()].copy_from_slice(raw_token.as_bytes());
-        ecb.encrypt_padded::<Pkcs7<U16>>(&mut buffer, raw_token.len());
+        ecb.encrypt_padded::<Pkcs7<U16>>(&mut buffer, raw_token.len())
+            .unwrap();
         hex::encode(&buffer)
     }
 
@@ -164,7 +174,9 @@ impl EcbUserOracle {
     pub fn try_admin_action(&self, token_hex: &str) -> bool {
         let mut ciphertext = hex::decode(token_hex);
         let mut ecb = Ecb::new(self.aes.clone());
-        let user_token = ecb.decrypt_padded::<Pkcs7<U16>>(&mut ciphertext);
+        let Ok(user_token) = ecb.decrypt_padded::<Pkcs7<U16>>(&mut ciphertext) else {
+            return false;
+        };
 
         let params = parse_url_params(&String::from_utf8_lossy(user_token));
         println!("params: {:#?}", params);
@@ -172,10 +184,23 @@ impl EcbUserOracle {
     }
 }
 
+const USERDATA_PREFIX: &str = "comment1=cooking%20MCs;userdata=";
+const USERDATA_SUFFIX: &str = ";comment2=%20like%20a%20pound%20of%20bacon";
+
+/// Quotes out `=` and `;` in `input` and embeds it between the fixed userdata prefix and suffix.
+fn format_userdata(input: &str) -> String {
+    let sanitized_input = input.replace('=', "\"=\"").replace(';', "\";\"");
+    format!("{}{}{}", USERDATA_PREFIX, sanitized_input, USERDATA_SUFFIX)
+}
+
+/// Checks whether the decrypted userdata string grants admin rights.
+fn is_admin_userdata(plaintext: &[u8]) -> bool {
+    String::from_utf8_lossy(plaintext).contains(";admin=true;")
+}
+
+/// Oracle for the CBC bitflipping attack (Challenge 16).
 pub struct CbcUserdataOracle {
     aes: Aes128,
-    prefix: String,
-    suffix: String,
 }
 
 impl CbcUserdataOracle {
@@ -186,20 +211,12 @@ impl CbcUserdataOracle {
         rng.fill_bytes(&mut key);
         let aes = Aes128::new(key.into());
 
-        let prefix = "comment1=cooking%20MCs;userdata=".to_string();
-        let suffix = ";comment2=%20like%20a%20pound%20of%20bacon".to_string();
-
-        Self {
-            aes,
-            prefix,
-            suffix,
-        }
+        Self { aes }
     }
 
     pub fn encrypt(&self, input: &str) -> String {
         // adapt plaintext
-        let sanitized_input = input.replace('=', "\"=\"").replace(';', "\";\"");
-        let plaintext = format!("{}{}{}", self.prefix, sanitized_input, self.suffix);
+        let plaintext = format_userdata(input);
         println!("plaintext: {}", plaintext);
 
         // encrypt
@@ -214,14 +231,327 @@ impl CbcUserdataOracle {
     pub fn try_admin_action(&self, ciphertext_hex: &str) -> bool {
         let mut ciphertext = hex::decode(ciphertext_hex);
         let mut cbc = Cbc::new(self.aes.clone(), [0; 16].into());
-        let plaintext = cbc.decrypt_padded::<Pkcs7<U16>>(&mut ciphertext);
-        let plaintext_str = String::from_utf8_lossy(plaintext);
-        plaintext_str.contains(";admin=true;")
+        let Ok(plaintext) = cbc.decrypt_padded::<Pkcs7<U16>>(&mut ciphertext) else {
+            return false;
+        };
+        is_admin_userdata(plaintext)
+    }
+}
+
+/// Oracle for the CTR bitflipping attack (Challenge 26).
+///
+/// Same userdata format as [`CbcUserdataOracle`], but encrypted with AES-CTR.
+pub struct CtrUserdataOracle {
+    aes: Aes128,
+    nonce: u64,
+}
+
+impl CtrUserdataOracle {
+    pub fn new() -> Self {
+        // initialize AES with random key
+        let mut rng = rand::rng();
+        let mut key = [0; 16];
+        rng.fill_bytes(&mut key);
+        let aes = Aes128::new(key.into());
+
+        Self {
+            aes,
+            nonce: rng.random(),
+        }
+    }
+
+    pub fn encrypt(&self, input: &str) -> String {
+        let mut buffer = format_userdata(input).into_bytes();
+        let mut ctr = Ctr::with_nonce(self.aes.clone(), self.nonce);
+        ctr.apply_keystream(&mut buffer);
+        hex::encode(&buffer)
+    }
+
+    pub fn try_admin_action(&self, ciphertext_hex: &str) -> bool {
+        let mut buffer = hex::decode(ciphertext_hex);
+        let mut ctr = Ctr::with_nonce(self.aes.clone(), self.nonce);
+        ctr.apply_keystream(&mut buffer);
+        is_admin_userdata(&buffer)
+    }
+}
+
+impl Default for CtrUserdataOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Errors returned by [`CbcKeyAsIvOracle::decrypt`].
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum KeyAsIvError {
+    /// The ciphertext could not be decrypted.
+    #[error("decryption failed: {0}")]
+    Decryption(#[from] cryptopals_modes::Error),
+    /// The plaintext contains non-ASCII bytes.
+    ///
+    /// The full plaintext is returned to the caller, which is what leaks the key.
+    #[error("plaintext contains non-ASCII bytes")]
+    NonAscii { plaintext: Vec<u8> },
+}
+
+/// Oracle for the CBC key recovery attack where the key is reused as IV (Challenge 27).
+///
+/// Same userdata format as [`CbcUserdataOracle`].
+pub struct CbcKeyAsIvOracle {
+    aes: Aes128,
+    key: [u8; 16],
+}
+
+impl CbcKeyAsIvOracle {
+    pub fn new() -> Self {
+        // initialize AES with random key
+        let mut rng = rand::rng();
+        let mut key = [0; 16];
+        rng.fill_bytes(&mut key);
+        let aes = Aes128::new(key.into());
+
+        Self { aes, key }
+    }
+
+    /// Encrypts the userdata string for `input` with AES-CBC, using the key as IV.
+    pub fn encrypt(&self, input: &str) -> Vec<u8> {
+        let plaintext = format_userdata(input);
+        let mut cbc = Cbc::new(self.aes.clone(), self.key.into());
+        let mut buffer = vec![0; (plaintext.len() + 1).next_multiple_of(16)];
+        buffer[..plaintext.len()].copy_from_slice(plaintext.as_bytes());
+        cbc.encrypt_padded::<Pkcs7<U16>>(&mut buffer, plaintext.len());
+        buffer
+    }
+
+    /// Decrypts `ciphertext` and checks that the resulting plaintext is pure ASCII.
+    ///
+    /// Returns the plaintext inside the error if it is not.
+    pub fn decrypt(&self, ciphertext: &[u8]) -> Result<String, KeyAsIvError> {
+        let mut buffer = ciphertext.to_vec();
+        let mut cbc = Cbc::new(self.aes.clone(), self.key.into());
+        let plaintext = cbc.decrypt_padded::<Pkcs7<U16>>(&mut buffer)?;
+        if !plaintext.is_ascii() {
+            return Err(KeyAsIvError::NonAscii {
+                plaintext: plaintext.to_vec(),
+            });
+        }
+        Ok(String::from_utf8_lossy(plaintext).to_string())
+    }
+}
+
+impl Default for CbcKeyAsIvOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Oracle for the CBC padding oracle attack (Challenge 17).
+pub struct CbcPaddingOracle {
+    aes: Aes128,
+}
+
+impl CbcPaddingOracle {
+    const PLAINTEXTS_BASE64: [&str; 10] = [
+        "MDAwMDAwTm93IHRoYXQgdGhlIHBhcnR5IGlzIGp1bXBpbmc=",
+        "MDAwMDAxV2l0aCB0aGUgYmFzcyBraWNrZWQgaW4gYW5kIHRoZSBWZWdhJ3MgYXJlIHB1bXBpbic=",
+        "MDAwMDAyUXVpY2sgdG8gdGhlIHBvaW50LCB0byB0aGUgcG9pbnQsIG5vIGZha2luZw==",
+        "MDAwMDAzQ29va2luZyBNQydzIGxpa2UgYSBwb3VuZCBvZiBiYWNvbg==",
+        "MDAwMDA0QnVybmluZyAnZW0sIGlmIHlvdSBhaW4ndCBxdWljayBhbmQgbmltYmxl",
+        "MDAwMDA1SSBnbyBjcmF6eSB3aGVuIEkgaGVhciBhIGN5bWJhbA==",
+        "MDAwMDA2QW5kIGEgaGlnaCBoYXQgd2l0aCBhIHNvdXBlZCB1cCB0ZW1wbw==",
+        "MDAwMDA3SSdtIG9uIGEgcm9sbCwgaXQncyB0aW1lIHRvIGdvIHNvbG8=",
+        "MDAwMDA4b2xsaW4nIGluIG15IGZpdmUgcG9pbnQgb2g=",
+        "MDAwMDA5aXRoIG15IHJhZy10b3AgZG93biBzbyBteSBoYWlyIGNhbiBibG93",
+    ];
+
+    /// Creates a new instance of the oracle with a random AES key.
+    pub fn new() -> Self {
+        // initialize AES with random key
+        let mut rng = rand::rng();
+        let mut key = [0; 16];
+        rng.fill_bytes(&mut key);
+        let aes = Aes128::new(key.into());
+
+        Self { aes }
+    }
+
+    /// Encrypts one of the ten Cryptopals strings, chosen at random, under a random IV.
+    ///
+    /// Returns `(iv, ciphertext)`.
+    pub fn encrypt(&self) -> ([u8; 16], Vec<u8>) {
+        let mut rng = rand::rng();
+        let plaintext_base64 = Self::PLAINTEXTS_BASE64.choose(&mut rng).unwrap();
+        let plaintext = base64::decode(plaintext_base64);
+
+        // create buffer
+        let len = plaintext.len();
+        let padding_len = 16 - len % 16;
+        let mut buffer = vec![0; len + padding_len];
+        buffer[..len].copy_from_slice(&plaintext);
+
+        // encrypt with random IV
+        let mut iv = [0; 16];
+        rng.fill_bytes(&mut iv);
+        let mut cbc = Cbc::new(self.aes.clone(), iv.into());
+        cbc.encrypt_padded::<Pkcs7<U16>>(&mut buffer, len);
+        (iv, buffer)
+    }
+
+    /// Decrypts `ciphertext` and checks whether it is correctly padded.
+    ///
+    /// This uses [`Pkcs7::unpad_checked`], which leaks the padding through its timing.
+    pub fn check_padding(&self, iv: &[u8], ciphertext: &[u8]) -> bool {
+        let Some(buffer) = self.decrypt(iv, ciphertext) else {
+            return false;
+        };
+        Pkcs7::<U16>::unpad_checked(&buffer).is_ok()
+    }
+
+    /// Decrypts `ciphertext` and checks whether it is correctly padded.
+    ///
+    /// This uses [`Pkcs7::unpad_constant_time`], so the timing side channel is closed.
+    /// However, the result itself still reveals whether the padding was valid.
+    pub fn check_padding_constant_time(&self, iv: &[u8], ciphertext: &[u8]) -> bool {
+        let Some(buffer) = self.decrypt(iv, ciphertext) else {
+            return false;
+        };
+        let (valid, _len) = Pkcs7::<U16>::unpad_constant_time(&buffer);
+        bool::from(valid)
+    }
+
+    fn decrypt(&self, iv: &[u8], ciphertext: &[u8]) -> Option<Vec<u8>> {
+        if ciphertext.is_empty() || !ciphertext.len().is_multiple_of(16) {
+            return None;
+        }
+        let iv: [u8; 16] = iv.try_into().ok()?;
+        let mut buffer = ciphertext.to_vec();
+        let mut cbc = Cbc::new(self.aes.clone(), iv.into());
+        cbc.decrypt(&mut buffer);
+        Some(buffer)
+    }
+}
+
+impl Default for CbcPaddingOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Oracle for the random access read/write AES-CTR attack (Challenge 25).
+pub struct CtrEditOracle {
+    aes: Aes128,
+    nonce: u64,
+}
+
+impl CtrEditOracle {
+    /// Creates a new instance of the oracle with a random AES key and nonce.
+    pub fn new() -> Self {
+        // initialize AES with random key
+        let mut rng = rand::rng();
+        let mut key = [0; 16];
+        rng.fill_bytes(&mut key);
+        let aes = Aes128::new(key.into());
+
+        Self {
+            aes,
+            nonce: rng.random(),
+        }
+    }
+
+    /// Encrypts `plaintext` with AES-CTR.
+    pub fn encrypt(&self, plaintext: &[u8]) -> Vec<u8> {
+        let mut buffer = plaintext.to_vec();
+        let mut ctr = Ctr::with_nonce(self.aes.clone(), self.nonce);
+        ctr.apply_keystream(&mut buffer);
+        buffer
+    }
+
+    /// Replaces the plaintext at `offset` with `newtext`, without touching the rest.
+    ///
+    /// Returns the edited ciphertext, which grows if `newtext` extends past its end.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `offset` lies past the end of `ciphertext`, since the gap would have no plaintext.
+    pub fn edit(&self, ciphertext: &[u8], offset: usize, newtext: &[u8]) -> Vec<u8> {
+        assert!(
+            offset <= ciphertext.len(),
+            "edit offset {offset} is past the end of the ciphertext"
+        );
+        let mut buffer = ciphertext.to_vec();
+        let end = offset + newtext.len();
+        if end > buffer.len() {
+            buffer.resize(end, 0);
+        }
+
+        // only the edited range is re-encrypted
+        let edited = &mut buffer[offset..end];
+        edited.copy_from_slice(newtext);
+        let mut ctr = Ctr::with_nonce(self.aes.clone(), self.nonce);
+        ctr.apply_keystream_at(edited, offset as u64);
+        buffer
+    }
+}
+
+impl Default for CtrEditOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Oracle for length extension attacks on secret-prefix MACs (Challenges 28-30).
+///
+/// Authenticates messages as `H(key || message)` with a random key of unknown length.
+pub struct SecretPrefixMac<H: MerkleDamgard> {
+    key: Vec<u8>,
+    _hash: PhantomData<H>,
+}
+
+impl<H: MerkleDamgard> SecretPrefixMac<H> {
+    /// Message for which the attacker is given a valid MAC.
+    pub const MESSAGE: &[u8] =
+        b"comment1=cooking%20MCs;userdata=foo;comment2=%20like%20a%20pound%20of%20bacon";
+
+    /// Creates a new instance of the oracle with a random key of 1 to 64 bytes.
+    pub fn new() -> Self {
+        let mut rng = rand::rng();
+        let mut key = vec![0; rng.random_range(1..=64)];
+        rng.fill_bytes(&mut key);
+        Self {
+            key,
+            _hash: PhantomData,
+        }
+    }
+
+    /// Computes the MAC of `message`.
+    pub fn mac(&self, message: &[u8]) -> Array<u8, H::OutputSize> {
+        let mut hasher = Hasher::<H>::new();
+        hasher.update(&self.key);
+        hasher.update(message);
+        hasher.finalize()
+    }
+
+    /// Checks whether `mac` is a valid MAC for `message`.
+    pub fn verify(&self, message: &[u8], mac: &[u8]) -> bool {
+        self.mac(message).as_slice() == mac
+    }
+
+    /// Checks whether `message` is authentic and grants admin rights.
+    pub fn try_admin_action(&self, message: &[u8], mac: &[u8]) -> bool {
+        self.verify(message, mac) && message.windows(11).any(|w| w == b";admin=true")
+    }
+}
+
+impl<H: MerkleDamgard> Default for SecretPrefixMac<H> {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use cryptopals_primitives::sha1::Sha1Core;
+
     use super::*;
 
     #[test]
@@ -233,4 +563,77 @@ mod tests {
         let token = oracle.create_admin("admin@test.com");
         assert!(oracle.try_admin_action(&token));
     }
+
+    #[test]
+    fn ctr_edit_oracle() {
+        let oracle = CtrEditOracle::new();
+        let ciphertext = oracle.encrypt(b"YELLOW SUBMARINE IN THE SEA");
+        let edited = oracle.edit(&ciphertext, 7, b"BANANA");
+        assert_eq!(edited, oracle.encrypt(b"YELLOW BANANAINE IN THE SEA"));
+        let extended = oracle.edit(&ciphertext, 27, b"!");
+        assert_eq!(extended, oracle.encrypt(b"YELLOW SUBMARINE IN THE SEA!"));
+    }
+
+    #[test]
+    #[should_panic(expected = "past the end")]
+    fn ctr_edit_oracle_rejects_gap() {
+        let oracle = CtrEditOracle::new();
+        let ciphertext = oracle.encrypt(b"YELLOW SUBMARINE");
+        oracle.edit(&ciphertext, 17, b"!");
+    }
+
+    #[test]
+    fn userdata_is_sanitized() {
+        let plaintext = format_userdata(";admin=true;");
+        assert!(!is_admin_userdata(plaintext.as_bytes()));
+        assert!(plaintext.starts_with(USERDATA_PREFIX));
+        assert!(plaintext.ends_with(USERDATA_SUFFIX));
+
+        let oracle = CtrUserdataOracle::new();
+        assert!(!oracle.try_admin_action(&oracle.encrypt(";admin=true;")));
+    }
+
+    #[test]
+    fn cbc_key_as_iv_oracle() {
+        let oracle = CbcKeyAsIvOracle::new();
+        let mut ciphertext = oracle.encrypt("hello");
+        assert_eq!(oracle.decrypt(&ciphertext), Ok(format_userdata("hello")));
+        ciphertext[0] ^= 0x80;
+        assert!(matches!(
+            oracle.decrypt(&ciphertext),
+            Err(KeyAsIvError::NonAscii { .. })
+        ));
+        assert!(matches!(
+            oracle.decrypt(&ciphertext[..20]),
+            Err(KeyAsIvError::Decryption(_))
+        ));
+    }
+
+    #[test]
+    fn secret_prefix_mac() {
+        let oracle = SecretPrefixMac::<Sha1Core>::new();
+        let message = SecretPrefixMac::<Sha1Core>::MESSAGE;
+        let mac = oracle.mac(message);
+        assert!(oracle.verify(message, &mac));
+        assert!(!oracle.verify(b"comment1=cooking", &mac));
+        assert!(!oracle.try_admin_action(message, &mac));
+
+        let forged = b";admin=true;";
+        assert!(!oracle.try_admin_action(forged, &mac));
+        assert!(oracle.try_admin_action(forged, &oracle.mac(forged)));
+    }
+
+    #[test]
+    fn cbc_padding_oracle() {
+        let oracle = CbcPaddingOracle::new();
+        let (iv, mut ciphertext) = oracle.encrypt();
+        assert!(oracle.check_padding(&iv, &ciphertext));
+        assert!(oracle.check_padding_constant_time(&iv, &ciphertext));
+
+        // flipping the last byte of the second to last block breaks the padding
+        let len = ciphertext.len();
+        ciphertext[len - 17] ^= 0x80;
+        assert!(!oracle.check_padding(&iv, &ciphertext));
+        assert!(!oracle.check_padding_constant_time(&iv, &ciphertext));
+    }
 }
@@ -0,0 +1,253 @@
+//! Attacks on the MT19937 Mersenne Twister.
+//!
+//! Recovering a timestamp seed by brute force (Challenge 22),
+//! cloning the generator from its outputs (Challenge 23),
+//! and breaking the MT19937 stream cipher and password reset tokens (Challenge 24).
+
+use std::cell::Cell;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use cryptopals_primitives::mt19937::{Mt19937, Mt19937Cipher};
+use rand::prelude::*;
+
+/// Number of 32-bit words in the MT19937 state.
+pub const STATE_WORDS: usize = 624;
+
+/// Source of the current time, in seconds since the Unix epoch.
+pub trait Clock {
+    fn now(&self) -> u64;
+}
+
+/// Clock backed by the system time.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+}
+
+/// Clock that only moves when told to, so waiting does not require sleeping.
+#[derive(Clone, Debug, Default)]
+pub struct MockClock {
+    now: Cell<u64>,
+}
+
+impl MockClock {
+    /// Creates a new clock starting at `now`.
+    pub fn new(now: u64) -> Self {
+        Self {
+            now: Cell::new(now),
+        }
+    }
+
+    /// Advances the clock by `secs` seconds.
+    pub fn advance(&self, secs: u64) {
+        self.now.set(self.now.get() + secs);
+    }
+}
+
+impl Clock for MockClock {
+    fn now(&self) -> u64 {
+        self.now.get()
+    }
+}
+
+/// Recovers the seed of an MT19937 that was seeded with a recent timestamp.
+///
+/// Tries every timestamp within the last `window` seconds according to `clock`,
+/// and checks whether the first output of the seeded generator equals `output`.
+///
+/// Returns the seed, if any was found.
+pub fn recover_seed_from_timestamp(output: u32, clock: &impl Clock, window: u64) -> Option<u32> {
+    let now = clock.now();
+    (now.saturating_sub(window)..=now)
+        .rev()
+        .map(|timestamp| timestamp as u32)
+        .find(|seed| Mt19937::new(*seed).next_output() == output)
+}
+
+/// Clones an MT19937 from 624 consecutive outputs.
+///
+/// Only the first 624 values of `outputs` are used.
+/// Returns a generator whose outputs continue right after them.
+///
+/// # Panics
+///
+/// Panics if `outputs` holds fewer than 624 values.
+pub fn clone(outputs: &[u32]) -> Mt19937 {
+    assert!(outputs.len() >= STATE_WORDS);
+    let state = core::array::from_fn(|i| untemper(outputs[i]));
+    Mt19937::from_state(state, STATE_WORDS)
+}
+
+/// Inverts the MT19937 tempering function, see [`Mt19937::temper`].
+pub fn untemper(y: u32) -> u32 {
+    let y = undo_right_shift_xor(y, 18);
+    let y = undo_left_shift_xor_and(y, 15, 0xefc60000);
+    let y = undo_left_shift_xor_and(y, 7, 0x9d2c5680);
+    undo_right_shift_xor(y, 11)
+}
+
+/// Inverts `y ^= y >> shift`.
+fn undo_right_shift_xor(value: u32, shift: u32) -> u32 {
+    // each iteration recovers another `shift` bits, starting from the top
+    let mut y = value;
+    for _ in 0..32 / shift {
+        y = value ^ (y >> shift);
+    }
+    y
+}
+
+/// Inverts `y ^= (y << shift) & mask`.
+fn undo_left_shift_xor_and(value: u32, shift: u32, mask: u32) -> u32 {
+    // each iteration recovers another `shift` bits, starting from the bottom
+    let mut y = value;
+    for _ in 0..32 / shift {
+        y = value ^ ((y << shift) & mask);
+    }
+    y
+}
+
+/// Oracle that encrypts attacker input under [`Mt19937Cipher`] with a random 16-bit seed.
+pub struct Mt19937CipherOracle {
+    cipher: Mt19937Cipher,
+}
+
+impl Mt19937CipherOracle {
+    /// Creates a new instance of the oracle with a random seed.
+    pub fn new() -> Self {
+        Self {
+            cipher: Mt19937Cipher::new(rand::rng().random()),
+        }
+    }
+
+    /// Encrypts `input`, prefixed by 5-40 random bytes.
+    pub fn encrypt(&self, input: &[u8]) -> Vec<u8> {
+        let mut rng = rand::rng();
+        let prefix_len = rng.random_range(5..=40);
+        let mut buffer = vec![0; prefix_len + input.len()];
+        rng.fill_bytes(&mut buffer[..prefix_len]);
+        buffer[prefix_len..].copy_from_slice(input);
+        self.cipher.apply_keystream(&mut buffer);
+        buffer
+    }
+}
+
+impl Default for Mt19937CipherOracle {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Recovers the 16-bit seed of an [`Mt19937Cipher`] by brute force.
+///
+/// The plaintext of `ciphertext` must end in `known_suffix`.
+///
+/// Returns the seed, if any was found.
+pub fn recover_cipher_seed(ciphertext: &[u8], known_suffix: &[u8]) -> Option<u16> {
+    assert!(known_suffix.len() <= ciphertext.len());
+    let suffix_start = ciphertext.len() - known_suffix.len();
+    (0..=u16::MAX).find(|seed| {
+        let mut buffer = ciphertext.to_vec();
+        Mt19937Cipher::new(*seed).apply_keystream(&mut buffer);
+        &buffer[suffix_start..] == known_suffix
+    })
+}
+
+/// Generates a password reset token of `len` bytes from an MT19937 seeded with the current time.
+///
+/// This is how tokens should NOT be generated, see [`is_mt19937_token`].
+pub fn generate_token(clock: &impl Clock, len: usize) -> Vec<u8> {
+    let mut token = vec![0; len];
+    Mt19937::new(clock.now() as u32).fill_bytes(&mut token);
+    token
+}
+
+/// Checks whether `token` was produced by [`generate_token`] within the last `time_window` seconds.
+///
+/// An empty token never counts as one, since it would trivially match every timestamp.
+pub fn is_mt19937_token(token: &[u8], clock: &impl Clock, time_window: u64) -> bool {
+    if token.is_empty() {
+        return false;
+    }
+    let now = clock.now();
+    let mut candidate = vec![0; token.len()];
+    (now.saturating_sub(time_window)..=now).any(|timestamp| {
+        Mt19937::new(timestamp as u32).fill_bytes(&mut candidate);
+        candidate == token
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn untemper_inverts_temper() {
+        let mut rng = rand::rng();
+        for _ in 0..1000 {
+            let y = rng.next_u32();
+            assert_eq!(untemper(Mt19937::temper(y)), y);
+        }
+    }
+
+    #[test]
+    fn clone_predicts_future_outputs() {
+        let mut rng = Mt19937::new(rand::rng().next_u32());
+        let outputs = (0..STATE_WORDS)
+            .map(|_| rng.next_output())
+            .collect::<Vec<_>>();
+        let mut cloned = clone(&outputs);
+        for _ in 0..2000 {
+            assert_eq!(cloned.next_output(), rng.next_output());
+        }
+    }
+
+    #[test]
+    fn timestamp_seed() {
+        let mut rng = rand::rng();
+        let clock = MockClock::new(1_700_000_000);
+
+        clock.advance(rng.random_range(40..=1000));
+        let seed = clock.now() as u32;
+        let output = Mt19937::new(seed).next_output();
+        clock.advance(rng.random_range(40..=1000));
+
+        assert_eq!(
+            recover_seed_from_timestamp(output, &clock, 2000),
+            Some(seed)
+        );
+        assert_eq!(recover_seed_from_timestamp(output, &clock, 10), None);
+    }
+
+    #[test]
+    fn cipher_seed() {
+        let oracle = Mt19937CipherOracle::new();
+        let known_plaintext = [b'A'; 14];
+        let ciphertext = oracle.encrypt(&known_plaintext);
+        let seed = recover_cipher_seed(&ciphertext, &known_plaintext).unwrap();
+
+        let mut plaintext = ciphertext.clone();
+        Mt19937Cipher::new(seed).apply_keystream(&mut plaintext);
+        assert!(plaintext.ends_with(&known_plaintext));
+    }
+
+    #[test]
+    fn password_reset_token() {
+        let clock = MockClock::new(1_700_000_000);
+        let token = generate_token(&clock, 16);
+        clock.advance(60);
+        assert!(is_mt19937_token(&token, &clock, 300));
+        assert!(!is_mt19937_token(&token, &clock, 30));
+
+        let mut random_token = [0; 16];
+        rand::rng().fill_bytes(&mut random_token);
+        assert!(!is_mt19937_token(&random_token, &clock, 300));
+        assert!(!is_mt19937_token(&[], &clock, 300));
+    }
+}
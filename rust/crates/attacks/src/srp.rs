@@ -0,0 +1,347 @@
+//! Secure Remote Password protocol (Challenges 36 to 38).
+//!
+//! The server only stores a salt and the verifier `v = g^x mod N` with `x = SHA256(salt || password)`.
+//! A login runs over a [`Connection`] and consists of:
+//!
+//! 1. C -> S: [`Message::Hello`] with the email `I` and `A = g^a mod N`
+//! 2. S -> C: [`Message::Challenge`] with the salt and `B = k*v + g^b mod N`,
+//!    or [`Message::SimplifiedChallenge`] with the salt, `B = g^b mod N` and a random `u`
+//! 3. C -> S: [`Message::Proof`] `HMAC-SHA256(K, salt)` with `K = SHA256(S)`
+//! 4. S -> C: [`Message::Result`] whether the proof was correct
+//!
+//! For SRP-6a both sides compute `k = SHA256(N || PAD(g))` and `u = SHA256(PAD(A) || PAD(B))`, as in [RFC 5054].
+//! Unlike the RFC, the server does not reject `A = 0 mod N`, which Challenge 37 exploits.
+//!
+//! [RFC 5054]: https://www.rfc-editor.org/rfc/rfc5054
+
+use std::collections::HashMap;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use cryptopals_bigint::BigUint;
+use cryptopals_bigint::dh::Group;
+use cryptopals_primitives::hmac::Hmac;
+use cryptopals_primitives::sha256::{Sha256, Sha256Core};
+use rand::prelude::*;
+use thiserror::Error;
+
+/// Length of the random salt in bytes.
+const SALT_LENGTH: usize = 16;
+
+/// Message exchanged between client and server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Message {
+    /// Email and public value `A` of the client.
+    Hello { email: String, public: BigUint },
+    /// Salt and public value `B` of an SRP-6a server.
+    Challenge { salt: Vec<u8>, public: BigUint },
+    /// Salt, public value `B` and scrambling parameter `u` of a simplified SRP server.
+    SimplifiedChallenge {
+        salt: Vec<u8>,
+        public: BigUint,
+        u: BigUint,
+    },
+    /// HMAC-SHA256 of the salt under the session key.
+    Proof(Vec<u8>),
+    /// Whether the server accepted the proof.
+    Result(bool),
+}
+
+/// Errors that can make either side abort a login.
+#[derive(Debug, Error)]
+pub enum SrpError {
+    #[error("the other side disconnected")]
+    Disconnected,
+    #[error("unexpected message: {0:?}")]
+    UnexpectedMessage(Message),
+    #[error("unknown user: {0}")]
+    UnknownUser(String),
+}
+
+/// Flavor of the protocol spoken by client and server.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variant {
+    /// SRP-6a, where `B` depends on the verifier and `u` on both public values.
+    Srp6a,
+    /// Simplified SRP from Challenge 38, where `B = g^b` and `u` is chosen by the server.
+    Simplified,
+}
+
+/// One end of an in-process, bidirectional message channel.
+pub struct Connection {
+    outgoing: Sender<Message>,
+    incoming: Receiver<Message>,
+}
+
+impl Connection {
+    /// Creates two connected ends.
+    pub fn pair() -> (Self, Self) {
+        let (a_tx, a_rx) = mpsc::channel();
+        let (b_tx, b_rx) = mpsc::channel();
+        let a = Self {
+            outgoing: a_tx,
+            incoming: b_rx,
+        };
+        let b = Self {
+            outgoing: b_tx,
+            incoming: a_rx,
+        };
+        (a, b)
+    }
+
+    /// Sends `message` to the other end.
+    pub fn send(&self, message: Message) -> Result<(), SrpError> {
+        self.outgoing
+            .send(message)
+            .map_err(|_| SrpError::Disconnected)
+    }
+
+    /// Waits for the next message from the other end.
+    pub fn receive(&self) -> Result<Message, SrpError> {
+        self.incoming.recv().map_err(|_| SrpError::Disconnected)
+    }
+}
+
+/// Salt and verifier stored for a single user.
+#[derive(Clone, Debug)]
+struct Record {
+    salt: Vec<u8>,
+    verifier: BigUint,
+}
+
+/// SRP server storing salts and verifiers, but no passwords.
+#[derive(Clone, Debug)]
+pub struct Server {
+    group: Group,
+    variant: Variant,
+    users: HashMap<String, Record>,
+}
+
+impl Server {
+    pub fn new(group: Group, variant: Variant) -> Self {
+        Self {
+            group,
+            variant,
+            users: HashMap::new(),
+        }
+    }
+
+    /// Stores a fresh salt and the verifier for `password` under `email`.
+    pub fn register(&mut self, email: &str, password: &str) {
+        let mut salt = vec![0; SALT_LENGTH];
+        rand::rng().fill_bytes(&mut salt);
+        let x = private_key(&salt, password);
+        let verifier = self.group.g.modpow(&x, &self.group.p);
+        self.users
+            .insert(email.to_string(), Record { salt, verifier });
+    }
+
+    /// Handles a single login on `connection`.
+    ///
+    /// Returns whether the client authenticated successfully.
+    pub fn serve(&self, connection: &Connection) -> Result<bool, SrpError> {
+        let (email, a) = match connection.receive()? {
+            Message::Hello { email, public } => (email, public),
+            message => return Err(SrpError::UnexpectedMessage(message)),
+        };
+        let Some(Record { salt, verifier }) = self.users.get(&email) else {
+            return Err(SrpError::UnknownUser(email));
+        };
+
+        let n = &self.group.p;
+        // all arithmetic is mod N anyway, but there is no check for A = 0 mod N
+        let a = &a % n;
+        let mut rng = rand::rng();
+        let b = BigUint::random_below(n, &mut rng);
+        let g_b = self.group.g.modpow(&b, n);
+        let u = match self.variant {
+            Variant::Srp6a => {
+                let b_public = &(&(&multiplier(&self.group) * verifier) + &g_b) % n;
+                let u = scrambler(n, &a, &b_public);
+                connection.send(Message::Challenge {
+                    salt: salt.clone(),
+                    public: b_public,
+                })?;
+                u
+            }
+            Variant::Simplified => {
+                let u = BigUint::random_below(&(BigUint::one() << 128), &mut rng);
+                connection.send(Message::SimplifiedChallenge {
+                    salt: salt.clone(),
+                    public: g_b,
+                    u: u.clone(),
+                })?;
+                u
+            }
+        };
+
+        // S = (A * v^u)^b
+        let s = (&(&a * &verifier.modpow(&u, n)) % n).modpow(&b, n);
+        let key = session_key(&s);
+        let valid = match connection.receive()? {
+            Message::Proof(proof) => {
+                let mut hmac = Hmac::<Sha256Core>::new(&key);
+                hmac.update(salt);
+                hmac.verify(&proof)
+            }
+            message => return Err(SrpError::UnexpectedMessage(message)),
+        };
+        connection.send(Message::Result(valid))?;
+        Ok(valid)
+    }
+
+    /// Opens a connection to this server, which handles a single login on a background thread.
+    ///
+    /// Returns whatever `client` returns after talking to the server.
+    pub fn connect<T>(&self, client: impl FnOnce(Connection) -> T) -> T {
+        let (client_end, server_end) = Connection::pair();
+        thread::scope(|s| {
+            // failed logins surface on the client side as a rejection or a disconnect
+            s.spawn(move || self.serve(&server_end));
+            client(client_end)
+        })
+    }
+}
+
+/// Logs in as `email` with `password`.
+///
+/// Returns whether the server accepted the login.
+pub fn login(
+    connection: &Connection,
+    group: &Group,
+    variant: Variant,
+    email: &str,
+    password: &str,
+) -> Result<bool, SrpError> {
+    let n = &group.p;
+    let a = BigUint::random_below(n, &mut rand::rng());
+    let a_public = group.g.modpow(&a, n);
+    connection.send(Message::Hello {
+        email: email.to_string(),
+        public: a_public.clone(),
+    })?;
+
+    let (salt, s) = match (variant, connection.receive()?) {
+        (Variant::Srp6a, Message::Challenge { salt, public }) => {
+            let x = private_key(&salt, password);
+            let u = scrambler(n, &a_public, &public);
+            // S = (B - k * g^x)^(a + u * x)
+            let k_g_x = &(&multiplier(group) * &group.g.modpow(&x, n)) % n;
+            let base = &(&(&public % n) + n) - &k_g_x;
+            let s = (&base % n).modpow(&(&a + &(&u * &x)), n);
+            (salt, s)
+        }
+        (Variant::Simplified, Message::SimplifiedChallenge { salt, public, u }) => {
+            let x = private_key(&salt, password);
+            // S = B^(a + u * x)
+            let s = public.modpow(&(&a + &(&u * &x)), n);
+            (salt, s)
+        }
+        (_, message) => return Err(SrpError::UnexpectedMessage(message)),
+    };
+
+    connection.send(Message::Proof(proof(&session_key(&s), &salt)))?;
+    match connection.receive()? {
+        Message::Result(valid) => Ok(valid),
+        message => Err(SrpError::UnexpectedMessage(message)),
+    }
+}
+
+/// Returns `x = SHA256(salt || password)`.
+pub fn private_key(salt: &[u8], password: &str) -> BigUint {
+    let mut sha = Sha256::new();
+    sha.update(salt);
+    sha.update(password.as_bytes());
+    BigUint::from_bytes_be(&sha.finalize())
+}
+
+/// Returns the session key `K = SHA256(S)`.
+pub fn session_key(s: &BigUint) -> Vec<u8> {
+    Sha256::digest(&s.to_bytes_be()).to_vec()
+}
+
+/// Returns the client's proof `HMAC-SHA256(K, salt)`.
+pub fn proof(key: &[u8], salt: &[u8]) -> Vec<u8> {
+    Hmac::<Sha256Core>::mac(key, salt).to_vec()
+}
+
+/// Returns the SRP-6a multiplier `k = SHA256(N || PAD(g))`.
+fn multiplier(group: &Group) -> BigUint {
+    let len = group.p.to_bytes_be().len();
+    let mut sha = Sha256::new();
+    sha.update(&group.p.to_bytes_be());
+    sha.update(&group.g.to_bytes_be_padded(len));
+    BigUint::from_bytes_be(&sha.finalize())
+}
+
+/// Returns the SRP-6a scrambling parameter `u = SHA256(PAD(A) || PAD(B))`.
+fn scrambler(n: &BigUint, a: &BigUint, b: &BigUint) -> BigUint {
+    let len = n.to_bytes_be().len();
+    let mut sha = Sha256::new();
+    sha.update(&a.to_bytes_be_padded(len));
+    sha.update(&b.to_bytes_be_padded(len));
+    BigUint::from_bytes_be(&sha.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn server(variant: Variant) -> Server {
+        let mut server = Server::new(Group::modp_1536(), variant);
+        server.register("alice@example.com", "hunter2");
+        server
+    }
+
+    #[test]
+    fn srp6a_login() {
+        let server = server(Variant::Srp6a);
+        let group = Group::modp_1536();
+        let login = |email: &str, password: &str| {
+            server.connect(|conn| login(&conn, &group, Variant::Srp6a, email, password))
+        };
+        assert!(login("alice@example.com", "hunter2").unwrap());
+        assert!(!login("alice@example.com", "hunter3").unwrap());
+        assert!(login("bob@example.com", "hunter2").is_err());
+    }
+
+    #[test]
+    fn simplified_login() {
+        let server = server(Variant::Simplified);
+        let group = Group::modp_1536();
+        let login = |password: &str| {
+            server.connect(|conn| {
+                login(
+                    &conn,
+                    &group,
+                    Variant::Simplified,
+                    "alice@example.com",
+                    password,
+                )
+            })
+        };
+        assert!(login("hunter2").unwrap());
+        assert!(!login("hunter3").unwrap());
+    }
+
+    #[test]
+    fn variant_mismatch() {
+        let server = server(Variant::Simplified);
+        let group = Group::modp_1536();
+        let result = server.connect(|conn| {
+            login(
+                &conn,
+                &group,
+                Variant::Srp6a,
+                "alice@example.com",
+                "hunter2",
+            )
+        });
+        assert!(matches!(
+            result,
+            Err(SrpError::UnexpectedMessage(
+                Message::SimplifiedChallenge { .. }
+            ))
+        ));
+    }
+}
@@ -0,0 +1,184 @@
+//! Diffie-Hellman Key Exchange
+//!
+//! Finite-field Diffie-Hellman over an arbitrary group `(p, g)`.
+//! The group is plain data, so protocols and attacks can substitute their own (malicious) parameters.
+//!
+//! The shared secret is turned into an AES-128 key by hashing its big-endian bytes
+//! and truncating the digest, as done throughout Cryptopals.
+
+use cryptopals_primitives::sha1::Sha1;
+use cryptopals_primitives::sha256::Sha256;
+use rand_core::RngCore;
+
+use crate::BigUint;
+
+/// 1536-bit MODP group prime, with generator 2.
+///
+/// Source: https://www.rfc-editor.org/rfc/rfc3526 (Section 2)
+pub const MODP_1536_PRIME: &str = "
+    FFFFFFFF FFFFFFFF C90FDAA2 2168C234 C4C6628B 80DC1CD1
+    29024E08 8A67CC74 020BBEA6 3B139B22 514A0879 8E3404DD
+    EF9519B3 CD3A431B 302B0A6D F25F1437 4FE1356D 6D51C245
+    E485B576 625E7EC6 F44C42E9 A637ED6B 0BFF5CB6 F406B7ED
+    EE386BFB 5A899FA5 AE9F2411 7C4B1FE6 49286651 ECE45B3D
+    C2007CB8 A163BF05 98DA4836 1C55D39A 69163FA8 FD24CF5F
+    83655D23 DCA3AD96 1C62F356 208552BB 9ED52907 7096966D
+    670C354E 4ABC9804 F1746C08 CA237327 FFFFFFFF FFFFFFFF";
+
+/// Diffie-Hellman group parameters.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Group {
+    /// Prime modulus.
+    pub p: BigUint,
+    /// Generator.
+    pub g: BigUint,
+}
+
+impl Group {
+    /// Creates a group from arbitrary parameters, which are not validated in any way.
+    pub fn new(p: BigUint, g: BigUint) -> Self {
+        Self { p, g }
+    }
+
+    /// Returns the RFC 3526 1536-bit MODP group used by Cryptopals.
+    pub fn modp_1536() -> Self {
+        Self::new(
+            BigUint::from_hex(MODP_1536_PRIME).expect("valid hex"),
+            BigUint::from(2u64),
+        )
+    }
+
+    /// Returns the toy group `p = 37, g = 5` from the start of Challenge 33.
+    pub fn toy() -> Self {
+        Self::new(BigUint::from(37u64), BigUint::from(5u64))
+    }
+
+    /// Generates a key pair with a private key uniformly chosen from `[1, p - 1)`.
+    pub fn generate_key_pair<R: RngCore + ?Sized>(&self, rng: &mut R) -> KeyPair {
+        let one = BigUint::one();
+        let range = &self.p - &(&one + &one);
+        let private = &BigUint::random_below(&range, rng) + &one;
+        self.key_pair_from_private(private)
+    }
+
+    /// Creates the key pair belonging to `private`.
+    pub fn key_pair_from_private(&self, private: BigUint) -> KeyPair {
+        let public = self.g.modpow(&private, &self.p);
+        KeyPair {
+            group: self.clone(),
+            private,
+            public,
+        }
+    }
+}
+
+/// Diffie-Hellman key pair within a specific group.
+#[derive(Clone, Debug)]
+pub struct KeyPair {
+    group: Group,
+    private: BigUint,
+    public: BigUint,
+}
+
+impl KeyPair {
+    /// Returns the group this key pair belongs to.
+    pub fn group(&self) -> &Group {
+        &self.group
+    }
+
+    /// Returns the private exponent.
+    pub fn private(&self) -> &BigUint {
+        &self.private
+    }
+
+    /// Returns the public value `g^private mod p`.
+    pub fn public(&self) -> &BigUint {
+        &self.public
+    }
+
+    /// Computes the shared secret `other_public^private mod p`.
+    ///
+    /// `other_public` is used as is, without checking that it is a valid group element.
+    pub fn shared_secret(&self, other_public: &BigUint) -> BigUint {
+        other_public.modpow(&self.private, &self.group.p)
+    }
+}
+
+/// Hash function used to derive an AES-128 key from a shared secret.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kdf {
+    /// First 16 bytes of SHA-1, as used in Challenges 34 and 35.
+    Sha1,
+    /// First 16 bytes of SHA-256.
+    Sha256,
+}
+
+impl Kdf {
+    /// Derives an AES-128 key from the big-endian encoding of `secret`.
+    pub fn derive_key(self, secret: &BigUint) -> [u8; 16] {
+        let bytes = secret.to_bytes_be();
+        let mut key = [0; 16];
+        match self {
+            Self::Sha1 => key.copy_from_slice(&Sha1::digest(&bytes)[..16]),
+            Self::Sha256 => key.copy_from_slice(&Sha256::digest(&bytes)[..16]),
+        }
+        key
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exchange(group: &Group) {
+        let mut rng = rand::rng();
+        let alice = group.generate_key_pair(&mut rng);
+        let bob = group.generate_key_pair(&mut rng);
+        let s_alice = alice.shared_secret(bob.public());
+        let s_bob = bob.shared_secret(alice.public());
+        assert_eq!(s_alice, s_bob);
+        assert!(s_alice < group.p);
+        assert_eq!(Kdf::Sha1.derive_key(&s_alice), Kdf::Sha1.derive_key(&s_bob));
+    }
+
+    #[test]
+    fn toy_group() {
+        exchange(&Group::toy());
+
+        // 5^4 = 33 and 5^10 = 30 (mod 37), and 5^40 = 5^4 since 5^36 = 1 (mod 37)
+        let group = Group::toy();
+        let alice = group.key_pair_from_private(BigUint::from(4u64));
+        let bob = group.key_pair_from_private(BigUint::from(10u64));
+        assert_eq!(alice.public(), &BigUint::from(33u64));
+        assert_eq!(bob.public(), &BigUint::from(30u64));
+        assert_eq!(alice.shared_secret(bob.public()), BigUint::from(33u64));
+    }
+
+    #[test]
+    fn modp_1536() {
+        let group = Group::modp_1536();
+        assert_eq!(group.p.bits(), 1536);
+        assert_eq!(group.g, BigUint::from(2u64));
+        exchange(&group);
+    }
+
+    #[test]
+    fn kdf() {
+        // SHA-1("abc") and SHA-256("abc"), truncated
+        let secret = BigUint::from_bytes_be(b"abc");
+        assert_eq!(
+            Kdf::Sha1.derive_key(&secret),
+            [
+                0xa9, 0x99, 0x3e, 0x36, 0x47, 0x06, 0x81, 0x6a, 0xba, 0x3e, 0x25, 0x71, 0x78, 0x50,
+                0xc2, 0x6c
+            ]
+        );
+        assert_eq!(
+            Kdf::Sha256.derive_key(&secret),
+            [
+                0xba, 0x78, 0x16, 0xbf, 0x8f, 0x01, 0xcf, 0xea, 0x41, 0x41, 0x40, 0xde, 0x5d, 0xae,
+                0x22, 0x23
+            ]
+        );
+    }
+}
@@ -0,0 +1,817 @@
+//! # Arbitrary-Precision Integers
+//!
+//! Unsigned big integers for the public-key parts of Cryptopals (Diffie-Hellman, SRP, RSA, DSA).
+//!
+//! [`BigUint`] stores its value as little-endian 64-bit limbs without leading zero limbs,
+//! so every value has exactly one representation and comparisons can work limb by limb.
+//! Modular exponentiation with odd moduli uses Montgomery multiplication, see [`montgomery`].
+//!
+//! None of this is constant time, which is fine for attacking toy protocols, but nothing else.
+//!
+//! ## Usage
+//!
+//! ```
+//! use cryptopals_bigint::BigUint;
+//!
+//! let p = BigUint::from(3233u64);
+//! let m = BigUint::from(65u64);
+//! let c = m.modpow(&BigUint::from(17u64), &p);
+//! assert_eq!(c, BigUint::from(2790u64));
+//! ```
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Rem, Shl, Shr, Sub};
+
+use cryptopals_utils::hex;
+use rand_core::RngCore;
+
+pub mod dh;
+pub mod montgomery;
+
+use montgomery::Montgomery;
+
+/// Arbitrary-precision unsigned integer.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    /// Little-endian limbs, without trailing zero limbs.
+    limbs: Vec<u64>,
+}
+
+impl BigUint {
+    /// Returns zero.
+    pub fn zero() -> Self {
+        Self { limbs: Vec::new() }
+    }
+
+    /// Returns one.
+    pub fn one() -> Self {
+        Self::from(1u64)
+    }
+
+    /// Creates a number from little-endian limbs, which may include leading zeros.
+    fn from_limbs(limbs: Vec<u64>) -> Self {
+        let mut n = Self { limbs };
+        n.normalize();
+        n
+    }
+
+    /// Removes leading zero limbs.
+    fn normalize(&mut self) {
+        while self.limbs.last() == Some(&0) {
+            self.limbs.pop();
+        }
+    }
+
+    /// Parses a big-endian byte string.
+    pub fn from_bytes_be(bytes: &[u8]) -> Self {
+        let limbs = bytes
+            .rchunks(8)
+            .map(|chunk| {
+                let mut limb = [0; 8];
+                limb[8 - chunk.len()..].copy_from_slice(chunk);
+                u64::from_be_bytes(limb)
+            })
+            .collect();
+        Self::from_limbs(limbs)
+    }
+
+    /// Returns the minimal big-endian byte representation, which is empty for zero.
+    pub fn to_bytes_be(&self) -> Vec<u8> {
+        self.limbs
+            .iter()
+            .rev()
+            .flat_map(|limb| limb.to_be_bytes())
+            .skip_while(|b| *b == 0)
+            .collect()
+    }
+
+    /// Returns the big-endian byte representation, left-padded with zeros to `len` bytes.
+    ///
+    /// Panics if the number does not fit into `len` bytes.
+    pub fn to_bytes_be_padded(&self, len: usize) -> Vec<u8> {
+        let bytes = self.to_bytes_be();
+        assert!(bytes.len() <= len, "number does not fit into {len} bytes");
+        let mut padded = vec![0; len - bytes.len()];
+        padded.extend_from_slice(&bytes);
+        padded
+    }
+
+    /// Parses a big-endian hex string, ignoring any whitespace.
+    ///
+    /// Returns `None` if the string contains any other non-hex characters.
+    pub fn from_hex(hex: &str) -> Option<Self> {
+        let hex = hex
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect::<String>();
+        if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        Some(Self::from_bytes_be(&hex::decode(&hex)))
+    }
+
+    /// Returns the lowercase hex representation, without leading zeros.
+    pub fn to_hex(&self) -> String {
+        format!("{self:x}")
+    }
+
+    /// Returns `true` if the number is zero.
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    /// Returns `true` if the number is odd.
+    pub fn is_odd(&self) -> bool {
+        self.limbs.first().is_some_and(|limb| limb & 1 == 1)
+    }
+
+    /// Returns `true` if the number is even.
+    pub fn is_even(&self) -> bool {
+        !self.is_odd()
+    }
+
+    /// Returns the number of significant bits, which is zero for zero.
+    pub fn bits(&self) -> usize {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() * 64 - top.leading_zeros() as usize,
+            None => 0,
+        }
+    }
+
+    /// Returns the bit at position `i`, counting from the least significant bit.
+    pub fn bit(&self, i: usize) -> bool {
+        self.limbs
+            .get(i / 64)
+            .is_some_and(|limb| (limb >> (i % 64)) & 1 == 1)
+    }
+
+    /// Returns the value as `u64`, if it fits.
+    pub fn to_u64(&self) -> Option<u64> {
+        match self.limbs[..] {
+            [] => Some(0),
+            [limb] => Some(limb),
+            _ => None,
+        }
+    }
+
+    /// Returns `self - other`, or `None` if the result would be negative.
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        if *self < *other {
+            return None;
+        }
+        let mut limbs = self.limbs.clone();
+        sub_assign_limbs(&mut limbs, &other.limbs);
+        Some(Self::from_limbs(limbs))
+    }
+
+    /// Returns quotient and remainder of the division by `divisor`.
+    ///
+    /// Panics if `divisor` is zero.
+    pub fn div_rem(&self, divisor: &Self) -> (Self, Self) {
+        assert!(!divisor.is_zero(), "division by zero");
+        if self < divisor {
+            return (Self::zero(), self.clone());
+        }
+        if let [d] = divisor.limbs[..] {
+            let (q, r) = div_rem_limb(&self.limbs, d);
+            return (Self::from_limbs(q), Self::from(r));
+        }
+        let (q, r) = div_rem_knuth(&self.limbs, &divisor.limbs);
+        (Self::from_limbs(q), Self::from_limbs(r))
+    }
+
+    /// Returns `self^exponent mod modulus`.
+    ///
+    /// Uses Montgomery multiplication for odd moduli,
+    /// and falls back to square-and-multiply with division for even ones.
+    pub fn modpow(&self, exponent: &Self, modulus: &Self) -> Self {
+        assert!(!modulus.is_zero(), "modulus is zero");
+        if modulus.is_odd() {
+            return Montgomery::new(modulus).pow(self, exponent);
+        }
+
+        let base = self % modulus;
+        let mut result = Self::one() % modulus;
+        for i in (0..exponent.bits()).rev() {
+            result = &(&result * &result) % modulus;
+            if exponent.bit(i) {
+                result = &(&result * &base) % modulus;
+            }
+        }
+        result
+    }
+
+    /// Returns the greatest common divisor of `self` and `other`.
+    pub fn gcd(&self, other: &Self) -> Self {
+        let mut a = self.clone();
+        let mut b = other.clone();
+        while !b.is_zero() {
+            let r = &a % &b;
+            a = b;
+            b = r;
+        }
+        a
+    }
+
+    /// Returns the inverse of `self` modulo `modulus`, or `None` if they are not coprime.
+    pub fn modinv(&self, modulus: &Self) -> Option<Self> {
+        // extended Euclid, tracking the coefficient of `self` modulo `modulus` to stay unsigned
+        let mut r0 = modulus.clone();
+        let mut r1 = self % modulus;
+        let mut t0 = Self::zero();
+        let mut t1 = Self::one();
+        while !r1.is_zero() {
+            let (q, r2) = r0.div_rem(&r1);
+            let qt1 = &(&q * &t1) % modulus;
+            let t2 = &(&t0 + modulus) - &qt1;
+            let t2 = &t2 % modulus;
+            r0 = r1;
+            r1 = r2;
+            t0 = t1;
+            t1 = t2;
+        }
+        (r0 == Self::one()).then_some(t0)
+    }
+
+    /// Returns the integer `n`-th root of `self`, rounded down.
+    pub fn nth_root(&self, n: u32) -> Self {
+        assert!(n > 0, "zeroth root");
+        if self.is_zero() || n == 1 {
+            return self.clone();
+        }
+
+        // Newton's method, starting above the root so it decreases monotonically
+        let n_big = Self::from(u64::from(n));
+        let n_minus_one = Self::from(u64::from(n - 1));
+        let mut x = Self::one() << self.bits().div_ceil(n as usize);
+        loop {
+            let y = &(&(&n_minus_one * &x) + &(self / &x.pow(n - 1))) / &n_big;
+            if y >= x {
+                return x;
+            }
+            x = y;
+        }
+    }
+
+    /// Returns the integer cube root of `self`, rounded down.
+    pub fn cbrt(&self) -> Self {
+        self.nth_root(3)
+    }
+
+    /// Returns a uniformly random number in `[0, bound)`.
+    ///
+    /// Panics if `bound` is zero.
+    pub fn random_below<R: RngCore + ?Sized>(bound: &Self, rng: &mut R) -> Self {
+        assert!(!bound.is_zero(), "empty range");
+        let bits = bound.bits();
+        let top_mask = u64::MAX >> ((64 - bits % 64) % 64);
+        // rejection sampling, succeeds with probability at least 1/2 per attempt
+        loop {
+            let mut limbs = vec![0; bits.div_ceil(64)];
+            limbs.iter_mut().for_each(|limb| *limb = rng.next_u64());
+            *limbs.last_mut().unwrap() &= top_mask;
+            let candidate = Self::from_limbs(limbs);
+            if candidate < *bound {
+                return candidate;
+            }
+        }
+    }
+
+    /// Returns `self^exponent`.
+    pub fn pow(&self, exponent: u32) -> Self {
+        let mut result = Self::one();
+        for i in (0..u32::BITS - exponent.leading_zeros()).rev() {
+            result = &result * &result;
+            if (exponent >> i) & 1 == 1 {
+                result = &result * self;
+            }
+        }
+        result
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> Self {
+        Self::from_limbs(vec![value])
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.limbs
+            .len()
+            .cmp(&other.limbs.len())
+            .then_with(|| self.limbs.iter().rev().cmp(other.limbs.iter().rev()))
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_zero() {
+            return f.pad_integral(true, "", "0");
+        }
+
+        // peel off 19 decimal digits at a time
+        const CHUNK: u64 = 10_000_000_000_000_000_000;
+        let mut chunks = Vec::new();
+        let mut rest = self.limbs.clone();
+        while !rest.is_empty() {
+            let (q, r) = div_rem_limb(&rest, CHUNK);
+            chunks.push(r);
+            rest = Self::from_limbs(q).limbs;
+        }
+        let mut s = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            s.push_str(&format!("{chunk:019}"));
+        }
+        f.pad_integral(true, "", &s)
+    }
+}
+
+impl fmt::LowerHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut s = match self.limbs.last() {
+            Some(top) => format!("{top:x}"),
+            None => "0".to_string(),
+        };
+        for limb in self.limbs.iter().rev().skip(1) {
+            s.push_str(&format!("{limb:016x}"));
+        }
+        f.pad_integral(true, "0x", &s)
+    }
+}
+
+impl fmt::Debug for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "BigUint({self:#x})")
+    }
+}
+
+impl Add<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        let (long, short) = if self.limbs.len() >= other.limbs.len() {
+            (self, other)
+        } else {
+            (other, self)
+        };
+        let mut limbs = long.limbs.clone();
+        let carry = add_assign_limbs(&mut limbs, &short.limbs);
+        if carry {
+            limbs.push(1);
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Sub<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    /// Panics if the result would be negative.
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other)
+            .expect("subtraction would be negative")
+    }
+}
+
+impl Mul<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        if self.is_zero() || other.is_zero() {
+            return BigUint::zero();
+        }
+        let mut limbs = vec![0; self.limbs.len() + other.limbs.len()];
+        for (i, &a) in self.limbs.iter().enumerate() {
+            let mut carry = 0u128;
+            for (j, &b) in other.limbs.iter().enumerate() {
+                let t = a as u128 * b as u128 + limbs[i + j] as u128 + carry;
+                limbs[i + j] = t as u64;
+                carry = t >> 64;
+            }
+            limbs[i + other.limbs.len()] = carry as u64;
+        }
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Div<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn div(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem<&BigUint> for &BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: &BigUint) -> BigUint {
+        self.div_rem(other).1
+    }
+}
+
+/// Forwards the owned variants of a binary operator to the implementation for references.
+macro_rules! forward_binop {
+    ($trait:ident, $method:ident) => {
+        impl $trait<BigUint> for BigUint {
+            type Output = BigUint;
+
+            fn $method(self, other: BigUint) -> BigUint {
+                (&self).$method(&other)
+            }
+        }
+
+        impl $trait<&BigUint> for BigUint {
+            type Output = BigUint;
+
+            fn $method(self, other: &BigUint) -> BigUint {
+                (&self).$method(other)
+            }
+        }
+
+        impl $trait<BigUint> for &BigUint {
+            type Output = BigUint;
+
+            fn $method(self, other: BigUint) -> BigUint {
+                self.$method(&other)
+            }
+        }
+    };
+}
+
+forward_binop!(Add, add);
+forward_binop!(Sub, sub);
+forward_binop!(Mul, mul);
+forward_binop!(Div, div);
+forward_binop!(Rem, rem);
+
+impl Shl<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shl(self, shift: usize) -> BigUint {
+        if self.is_zero() {
+            return BigUint::zero();
+        }
+        let (limb_shift, bit_shift) = (shift / 64, shift % 64);
+        let mut limbs = vec![0; limb_shift];
+        limbs.extend_from_slice(&self.limbs);
+        limbs.push(0);
+        shl_limbs(&mut limbs[limb_shift..], bit_shift);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Shl<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shl(self, shift: usize) -> BigUint {
+        &self << shift
+    }
+}
+
+impl Shr<usize> for &BigUint {
+    type Output = BigUint;
+
+    fn shr(self, shift: usize) -> BigUint {
+        let (limb_shift, bit_shift) = (shift / 64, shift % 64);
+        if limb_shift >= self.limbs.len() {
+            return BigUint::zero();
+        }
+        let mut limbs = self.limbs[limb_shift..].to_vec();
+        shr_limbs(&mut limbs, bit_shift);
+        BigUint::from_limbs(limbs)
+    }
+}
+
+impl Shr<usize> for BigUint {
+    type Output = BigUint;
+
+    fn shr(self, shift: usize) -> BigUint {
+        &self >> shift
+    }
+}
+
+/// Adds `b` onto `a`, which has to be at least as long, and returns the final carry.
+fn add_assign_limbs(a: &mut [u64], b: &[u64]) -> bool {
+    let mut carry = false;
+    for (i, limb) in a.iter_mut().enumerate() {
+        let b = b.get(i).copied().unwrap_or(0);
+        let (sum, c1) = limb.overflowing_add(b);
+        let (sum, c2) = sum.overflowing_add(carry as u64);
+        *limb = sum;
+        carry = c1 || c2;
+    }
+    carry
+}
+
+/// Subtracts `b` from `a` in place, assuming `a >= b`.
+fn sub_assign_limbs(a: &mut [u64], b: &[u64]) {
+    let mut borrow = false;
+    for (i, limb) in a.iter_mut().enumerate() {
+        let b = b.get(i).copied().unwrap_or(0);
+        let (diff, b1) = limb.overflowing_sub(b);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        *limb = diff;
+        borrow = b1 || b2;
+    }
+    debug_assert!(!borrow);
+}
+
+/// Shifts `limbs` left by `shift < 64` bits, dropping bits shifted out of the top limb.
+fn shl_limbs(limbs: &mut [u64], shift: usize) {
+    if shift == 0 {
+        return;
+    }
+    for i in (0..limbs.len()).rev() {
+        let lower = if i > 0 {
+            limbs[i - 1] >> (64 - shift)
+        } else {
+            0
+        };
+        limbs[i] = (limbs[i] << shift) | lower;
+    }
+}
+
+/// Shifts `limbs` right by `shift < 64` bits.
+fn shr_limbs(limbs: &mut [u64], shift: usize) {
+    if shift == 0 {
+        return;
+    }
+    for i in 0..limbs.len() {
+        let upper = limbs.get(i + 1).map_or(0, |limb| limb << (64 - shift));
+        limbs[i] = (limbs[i] >> shift) | upper;
+    }
+}
+
+/// Divides `a` by the single limb `d`, returning quotient limbs and remainder.
+fn div_rem_limb(a: &[u64], d: u64) -> (Vec<u64>, u64) {
+    let mut q = vec![0; a.len()];
+    let mut r = 0u128;
+    for i in (0..a.len()).rev() {
+        let t = (r << 64) | a[i] as u128;
+        q[i] = (t / d as u128) as u64;
+        r = t % d as u128;
+    }
+    (q, r as u64)
+}
+
+/// Divides `a` by `b` (at least two limbs, normalized) with Knuth's Algorithm D.
+///
+/// See The Art of Computer Programming, Vol. 2, Section 4.3.1.
+fn div_rem_knuth(a: &[u64], b: &[u64]) -> (Vec<u64>, Vec<u64>) {
+    // normalize so the top bit of the divisor is set
+    let shift = b.last().unwrap().leading_zeros() as usize;
+    let mut v = b.to_vec();
+    shl_limbs(&mut v, shift);
+    let mut u = a.to_vec();
+    u.push(0);
+    shl_limbs(&mut u, shift);
+
+    let n = v.len();
+    let m = u.len() - n;
+    let mut q = vec![0; m];
+    let (v_top, v_next) = (v[n - 1] as u128, v[n - 2] as u128);
+    for j in (0..m).rev() {
+        // estimate quotient digit from the top limbs, it is at most two too large
+        let numerator = ((u[j + n] as u128) << 64) | u[j + n - 1] as u128;
+        let mut q_hat = numerator / v_top;
+        let mut r_hat = numerator % v_top;
+        while q_hat >> 64 != 0 || q_hat * v_next > ((r_hat << 64) | u[j + n - 2] as u128) {
+            q_hat -= 1;
+            r_hat += v_top;
+            if r_hat >> 64 != 0 {
+                break;
+            }
+        }
+
+        // multiply and subtract
+        let mut carry = 0u128;
+        let mut borrow = false;
+        for i in 0..n {
+            let product = q_hat * v[i] as u128 + carry;
+            carry = product >> 64;
+            let (diff, b1) = u[i + j].overflowing_sub(product as u64);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            u[i + j] = diff;
+            borrow = b1 || b2;
+        }
+        let (diff, b1) = u[j + n].overflowing_sub(carry as u64);
+        let (diff, b2) = diff.overflowing_sub(borrow as u64);
+        u[j + n] = diff;
+
+        // the estimate was one too large, add back
+        if b1 || b2 {
+            q_hat -= 1;
+            let carry = add_assign_limbs(&mut u[j..j + n], &v);
+            u[j + n] = u[j + n].wrapping_add(carry as u64);
+        }
+        q[j] = q_hat as u64;
+    }
+
+    u.truncate(n);
+    shr_limbs(&mut u, shift);
+    (q, u)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 1536-bit MODP group prime.
+    ///
+    /// Source: https://www.rfc-editor.org/rfc/rfc3526 (Section 2)
+    const MODP_1536: &str = "
+        FFFFFFFF FFFFFFFF C90FDAA2 2168C234 C4C6628B 80DC1CD1
+        29024E08 8A67CC74 020BBEA6 3B139B22 514A0879 8E3404DD
+        EF9519B3 CD3A431B 302B0A6D F25F1437 4FE1356D 6D51C245
+        E485B576 625E7EC6 F44C42E9 A637ED6B 0BFF5CB6 F406B7ED
+        EE386BFB 5A899FA5 AE9F2411 7C4B1FE6 49286651 ECE45B3D
+        C2007CB8 A163BF05 98DA4836 1C55D39A 69163FA8 FD24CF5F
+        83655D23 DCA3AD96 1C62F356 208552BB 9ED52907 7096966D
+        670C354E 4ABC9804 F1746C08 CA237327 FFFFFFFF FFFFFFFF";
+
+    const DH_2_POW_A: &str = "
+        cfb04866b536139aba12c483175c92b1289b2d86c8d0d39d66bdb72413c828a9
+        1ebf0abe7874866946e48658dab1cb9f51e5a8f1faec472e40015aeef100de5e
+        758ef44e288817cc3e0020799a86765ebc1216289e0063d567cf57e970d4f617
+        b6e0712f25eae528abf17a4588122abe91976ab23b564827d75f24e9557b43b4
+        4218496d888d302cd6dff57aea581e8749d31dbdd1f8a77319d33c129f3ff07f
+        bbb129a7a891679959725f679fcaca282aa5af983c419f2ef80d5c94d8186c59";
+
+    #[test]
+    fn bytes_and_hex() {
+        let n = BigUint::from_hex("0102030405060708090a").unwrap();
+        assert_eq!(n.to_bytes_be(), (1..=10).collect::<Vec<u8>>());
+        assert_eq!(n.to_hex(), "102030405060708090a");
+        assert_eq!(format!("{n:#x}"), "0x102030405060708090a");
+        assert_eq!(n.to_bytes_be_padded(12)[..2], [0, 0]);
+        assert_eq!(BigUint::from_bytes_be(&[0, 0, 5]), BigUint::from(5u64));
+        assert!(BigUint::from_bytes_be(&[]).is_zero());
+        assert_eq!(BigUint::zero().to_bytes_be(), Vec::<u8>::new());
+        assert_eq!(BigUint::from_hex(MODP_1536).unwrap().bits(), 1536);
+        assert_eq!(BigUint::from_hex("12 3g"), None);
+    }
+
+    #[test]
+    fn display() {
+        let n = BigUint::from(u64::MAX) * BigUint::from(u64::MAX);
+        assert_eq!(n.to_string(), "340282366920938463426481119284349108225");
+        assert_eq!(BigUint::zero().to_string(), "0");
+        assert_eq!(BigUint::from(12345u64).to_string(), "12345");
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = BigUint::from_hex("ffffffffffffffffffffffffffffffff").unwrap();
+        let one = BigUint::one();
+        let b = &a + &one;
+        assert_eq!(b, BigUint::one() << 128);
+        assert_eq!(&b - &one, a);
+        assert_eq!(b.checked_sub(&(&b + &one)), None);
+        assert_eq!(&b >> 127, BigUint::from(2u64));
+        assert_eq!(&b >> 200, BigUint::zero());
+
+        // (2^128 - 1)^2 = 2^256 - 2^129 + 1
+        let square = &a * &a;
+        assert_eq!(square, ((&one << 256) - (&one << 129)) + &one);
+        assert_eq!(square.div_rem(&a), (a.clone(), BigUint::zero()));
+        assert_eq!((&square + &BigUint::from(7u64)) % &a, BigUint::from(7u64));
+    }
+
+    #[test]
+    fn division_matches_multiplication() {
+        // edge cases for the quotient estimate of Algorithm D
+        let cases = [
+            (
+                "1000000000000000000000000000000000000000000000000",
+                "10000000000000001",
+            ),
+            (
+                "7fffffff800000010000000000000000",
+                "800000008000000200000005",
+            ),
+            (
+                "fffffffffffffffffffffffffffffffeffffffffffffffff",
+                "ffffffffffffffffffffffff",
+            ),
+            (
+                MODP_1536,
+                "c90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74",
+            ),
+        ];
+        for (a, b) in cases {
+            let (a, b) = (BigUint::from_hex(a).unwrap(), BigUint::from_hex(b).unwrap());
+            let (q, r) = a.div_rem(&b);
+            assert!(r < b);
+            assert_eq!(&(&q * &b) + &r, a);
+        }
+    }
+
+    /// Textbook RSA example with `p = 61` and `q = 53`.
+    #[test]
+    fn rsa_small() {
+        let n = BigUint::from(3233u64);
+        let phi = BigUint::from(3120u64);
+        let e = BigUint::from(17u64);
+        let d = e.modinv(&phi).unwrap();
+        assert_eq!(d, BigUint::from(2753u64));
+
+        let m = BigUint::from(65u64);
+        let c = m.modpow(&e, &n);
+        assert_eq!(c, BigUint::from(2790u64));
+        assert_eq!(c.modpow(&d, &n), m);
+    }
+
+    /// RSA with the Mersenne primes `2^521 - 1` and `2^607 - 1`.
+    #[test]
+    fn rsa_mersenne() {
+        let one = BigUint::one();
+        let p = (&one << 521) - &one;
+        let q = (&one << 607) - &one;
+        let n = &p * &q;
+        let phi = (&p - &one) * (&q - &one);
+        let e = BigUint::from(65537u64);
+        let d = e.modinv(&phi).unwrap();
+        assert_eq!(&(&e * &d) % &phi, one);
+
+        let m = BigUint::from_bytes_be(b"Mersenne primes make fine RSA factors");
+        let c = m.modpow(&e, &n);
+        assert_ne!(c, m);
+        assert_eq!(c.modpow(&d, &n), m);
+    }
+
+    /// Reference values computed independently with Python's built-in `pow`.
+    #[test]
+    fn modpow_dh() {
+        let p = BigUint::from_hex(MODP_1536).unwrap();
+        let g = BigUint::from(2u64);
+        let a = BigUint::from_hex("deadbeefcafebabe0123456789abcdef").unwrap();
+        let expected = BigUint::from_hex(DH_2_POW_A).unwrap();
+        assert_eq!(g.modpow(&a, &p), expected);
+
+        // Fermat: g^(p-1) = 1 mod p
+        assert_eq!(g.modpow(&(&p - &BigUint::one()), &p), BigUint::one());
+    }
+
+    #[test]
+    fn modpow_even_modulus() {
+        let m = BigUint::from(1u64 << 40);
+        let b = BigUint::from(3u64);
+        let e = BigUint::from(1000u64);
+        let mut expected = 1u64;
+        for _ in 0..1000 {
+            expected = expected.wrapping_mul(3) & ((1 << 40) - 1);
+        }
+        assert_eq!(b.modpow(&e, &m), BigUint::from(expected));
+        assert_eq!(b.modpow(&BigUint::zero(), &BigUint::one()), BigUint::zero());
+    }
+
+    #[test]
+    fn gcd_and_inverse() {
+        let a = BigUint::from(2u64 * 3 * 5 * 7 * 11);
+        let b = BigUint::from(3u64 * 7 * 13);
+        assert_eq!(a.gcd(&b), BigUint::from(21u64));
+        assert_eq!(a.modinv(&b), None);
+        assert_eq!(
+            BigUint::from(3u64).modinv(&BigUint::from(11u64)),
+            Some(BigUint::from(4u64))
+        );
+    }
+
+    #[test]
+    fn random_below() {
+        let mut rng = rand::rng();
+        let bound = BigUint::from_hex("10000000000000000000000000001").unwrap();
+        for _ in 0..100 {
+            assert!(BigUint::random_below(&bound, &mut rng) < bound);
+        }
+        let small = BigUint::from(3u64);
+        let mut seen = [false; 3];
+        for _ in 0..100 {
+            seen[BigUint::random_below(&small, &mut rng).to_u64().unwrap() as usize] = true;
+        }
+        assert_eq!(seen, [true; 3]);
+    }
+
+    #[test]
+    fn roots() {
+        let x = BigUint::from_hex("123456789abcdef0123456789abcdef").unwrap();
+        let cube = x.pow(3);
+        assert_eq!(cube.cbrt(), x);
+        assert_eq!((&cube - &BigUint::one()).cbrt(), &x - &BigUint::one());
+        assert_eq!((&cube + &BigUint::one()).cbrt(), x);
+        assert_eq!(x.pow(5).nth_root(5), x);
+        assert_eq!(BigUint::from(26u64).cbrt(), BigUint::from(2u64));
+        assert_eq!(BigUint::from(27u64).cbrt(), BigUint::from(3u64));
+        assert_eq!(BigUint::one().nth_root(7), BigUint::one());
+    }
+}
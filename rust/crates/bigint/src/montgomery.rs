@@ -0,0 +1,166 @@
+//! Montgomery Multiplication
+//!
+//! Modular multiplication without division, for a fixed odd modulus `n`.
+//! Numbers are kept in Montgomery form `a * R mod n` with `R = 2^(64 * s)`,
+//! where `s` is the number of limbs of `n`, so reductions only need shifts by whole limbs.
+//!
+//! Multiplication uses the CIOS method from Koç, Acar and Kaliski,
+//! "Analyzing and Comparing Montgomery Multiplication Algorithms" (1996).
+
+use crate::BigUint;
+
+/// Precomputed context for arithmetic modulo a fixed odd modulus.
+#[derive(Clone, Debug)]
+pub struct Montgomery {
+    modulus: BigUint,
+    /// Limbs of the modulus.
+    n: Vec<u64>,
+    /// `-n^-1 mod 2^64`.
+    n_prime: u64,
+    /// `R^2 mod n`, used to convert into Montgomery form.
+    r_squared: Vec<u64>,
+}
+
+impl Montgomery {
+    /// Creates a new context for `modulus`.
+    ///
+    /// Panics if `modulus` is even.
+    pub fn new(modulus: &BigUint) -> Self {
+        assert!(modulus.is_odd(), "Montgomery modulus must be odd");
+        let n = modulus.limbs.clone();
+
+        // Newton iteration doubles the number of correct low bits each step
+        let mut inverse = 1u64;
+        for _ in 0..6 {
+            inverse = inverse.wrapping_mul(2u64.wrapping_sub(n[0].wrapping_mul(inverse)));
+        }
+
+        let r_squared = &(BigUint::one() << (128 * n.len())) % modulus;
+        let r_squared = pad(&r_squared, n.len());
+        Self {
+            modulus: modulus.clone(),
+            n,
+            n_prime: inverse.wrapping_neg(),
+            r_squared,
+        }
+    }
+
+    /// Returns the modulus.
+    pub fn modulus(&self) -> &BigUint {
+        &self.modulus
+    }
+
+    /// Returns `base^exponent mod n`.
+    pub fn pow(&self, base: &BigUint, exponent: &BigUint) -> BigUint {
+        if self.modulus == BigUint::one() {
+            return BigUint::zero();
+        }
+
+        let base = pad(&(base % &self.modulus), self.n.len());
+        let base = self.mul(&base, &self.r_squared);
+        let mut one = vec![0; self.n.len()];
+        one[0] = 1;
+        let mut result = self.mul(&one, &self.r_squared);
+
+        for i in (0..exponent.bits()).rev() {
+            result = self.mul(&result, &result);
+            if exponent.bit(i) {
+                result = self.mul(&result, &base);
+            }
+        }
+        BigUint::from_limbs(self.mul(&result, &one))
+    }
+
+    /// Returns `a * b * R^-1 mod n` for `a, b < n`, both given as exactly `s` limbs.
+    fn mul(&self, a: &[u64], b: &[u64]) -> Vec<u64> {
+        let s = self.n.len();
+        let mut t = vec![0u64; s + 2];
+        for &a_i in a {
+            // t += a_i * b
+            let mut carry = 0u128;
+            for j in 0..s {
+                let x = t[j] as u128 + a_i as u128 * b[j] as u128 + carry;
+                t[j] = x as u64;
+                carry = x >> 64;
+            }
+            let x = t[s] as u128 + carry;
+            t[s] = x as u64;
+            t[s + 1] = (x >> 64) as u64;
+
+            // t = (t + m * n) / 2^64, where m makes the lowest limb vanish
+            let m = t[0].wrapping_mul(self.n_prime);
+            let x = t[0] as u128 + m as u128 * self.n[0] as u128;
+            let mut carry = x >> 64;
+            for j in 1..s {
+                let x = t[j] as u128 + m as u128 * self.n[j] as u128 + carry;
+                t[j - 1] = x as u64;
+                carry = x >> 64;
+            }
+            let x = t[s] as u128 + carry;
+            t[s - 1] = x as u64;
+            t[s] = t[s + 1] + (x >> 64) as u64;
+        }
+
+        // result is below 2n, so a single conditional subtraction suffices
+        t.truncate(s + 1);
+        if t[s] != 0 || !less_than(&t[..s], &self.n) {
+            let mut borrow = false;
+            for (t_j, &n_j) in t.iter_mut().zip(&self.n) {
+                let (diff, b1) = t_j.overflowing_sub(n_j);
+                let (diff, b2) = diff.overflowing_sub(borrow as u64);
+                *t_j = diff;
+                borrow = b1 || b2;
+            }
+        }
+        t.truncate(s);
+        t
+    }
+}
+
+/// Returns the limbs of `a`, zero-extended to `len` limbs.
+fn pad(a: &BigUint, len: usize) -> Vec<u64> {
+    let mut limbs = a.limbs.clone();
+    limbs.resize(len, 0);
+    limbs
+}
+
+/// Compares two little-endian limb slices of equal length.
+fn less_than(a: &[u64], b: &[u64]) -> bool {
+    a.iter().rev().lt(b.iter().rev())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_naive_modpow() {
+        let modulus =
+            BigUint::from_hex("f123456789abcdef0123456789abcdef0123456789abcdef1").unwrap();
+        let ctx = Montgomery::new(&modulus);
+        let base = BigUint::from_hex("abcdef0123456789abcdef").unwrap();
+        for exponent in [0u64, 1, 2, 3, 65537, u64::MAX] {
+            let exponent = BigUint::from(exponent);
+            let mut expected = BigUint::one();
+            for i in (0..exponent.bits()).rev() {
+                expected = &(&expected * &expected) % &modulus;
+                if exponent.bit(i) {
+                    expected = &(&expected * &base) % &modulus;
+                }
+            }
+            assert_eq!(ctx.pow(&base, &exponent), expected);
+        }
+    }
+
+    #[test]
+    fn single_limb() {
+        let ctx = Montgomery::new(&BigUint::from(1_000_000_007u64));
+        let result = ctx.pow(&BigUint::from(2u64), &BigUint::from(1_000_000_006u64));
+        assert_eq!(result, BigUint::one());
+        let ctx = Montgomery::new(&BigUint::one());
+        assert_eq!(
+            ctx.pow(&BigUint::from(5u64), &BigUint::one()),
+            BigUint::zero()
+        );
+    }
+}
@@ -2,28 +2,35 @@
 //!
 //! ## Usage
 
-use cryptopals_padding::{Padding, pkcs7::Pkcs7};
+use core::marker::PhantomData;
+
+use cryptopals_padding::Padding;
 use cryptopals_primitives::{BlockCipher, xor};
-use hybrid_array::sizes::U16;
 use hybrid_array::{Array, ArraySize};
 
-// TODO: generalize over block size
-pub struct Cbc<C: BlockCipher<U16, U16>> {
+use crate::{Result, check_block_aligned};
+
+/// CBC mode over a block cipher with block size `B` and key size `K`.
+pub struct Cbc<C: BlockCipher<B, K>, B: ArraySize, K: ArraySize> {
     cipher: C,
-    // TODO: use actual block size here
-    iv: Array<u8, U16>,
+    iv: Array<u8, B>,
+    _key_size: PhantomData<K>,
 }
 
-impl<C: BlockCipher<U16, U16>> Cbc<C> {
+impl<C: BlockCipher<B, K>, B: ArraySize, K: ArraySize> Cbc<C, B, K> {
     ///
-    pub fn new(cipher: C, iv: Array<u8, U16>) -> Self {
-        Self { cipher, iv }
+    pub fn new(cipher: C, iv: Array<u8, B>) -> Self {
+        Self {
+            cipher,
+            iv,
+            _key_size: PhantomData,
+        }
     }
 
     ///
     pub fn encrypt(&mut self, bytes: &mut [u8], len: usize) {
         assert!(bytes.len().is_multiple_of(C::BLOCK_SIZE));
-        let mut tmp = self.iv;
+        let mut tmp = self.iv.clone();
         for chunk in bytes.chunks_exact_mut(C::BLOCK_SIZE) {
             xor::encrypt_fixed(chunk, &tmp);
             self.cipher.encrypt_block_in_place(chunk);
@@ -32,7 +39,7 @@ impl<C: BlockCipher<U16, U16>> Cbc<C> {
     }
 
     ///
-    pub fn encrypt_padded<P: Padding<U16>>(&mut self, bytes: &mut [u8], len: usize) {
+    pub fn encrypt_padded<P: Padding<B>>(&mut self, bytes: &mut [u8], len: usize) {
         assert!(bytes.len().is_multiple_of(C::BLOCK_SIZE));
         P::pad_bytes(bytes, len);
         self.encrypt(bytes, len);
@@ -41,8 +48,8 @@ impl<C: BlockCipher<U16, U16>> Cbc<C> {
     ///
     pub fn decrypt(&mut self, bytes: &mut [u8]) {
         assert!(bytes.len().is_multiple_of(C::BLOCK_SIZE));
-        let mut current_xor_key = self.iv;
-        let mut next_xor_key = self.iv;
+        let mut current_xor_key = self.iv.clone();
+        let mut next_xor_key = self.iv.clone();
         for chunk in bytes.chunks_exact_mut(C::BLOCK_SIZE) {
             next_xor_key.copy_from_slice(chunk);
             self.cipher.decrypt_block_in_place(chunk);
@@ -51,19 +58,102 @@ impl<C: BlockCipher<U16, U16>> Cbc<C> {
         }
     }
 
+    /// Decrypts `bytes` in place and returns the plaintext with padding `P` removed.
     ///
-    pub fn decrypt_padded<'a, P: Padding<U16>>(&mut self, bytes: &'a mut [u8]) -> &'a [u8] {
-        assert!(bytes.len().is_multiple_of(C::BLOCK_SIZE));
+    /// Returns an error if `bytes` is not a multiple of the block size,
+    /// or if the decrypted plaintext is not correctly padded.
+    pub fn decrypt_padded<'a, P: Padding<B>>(&mut self, bytes: &'a mut [u8]) -> Result<&'a [u8]> {
+        check_block_aligned(bytes, C::BLOCK_SIZE)?;
         self.decrypt(bytes);
-        let unpadded_len = P::unpad_bytes(bytes).len();
-        &bytes[..unpadded_len]
+        let unpadded_len = P::unpad_bytes(bytes)?.len();
+        Ok(&bytes[..unpadded_len])
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use cryptopals_padding::ansi_x923::AnsiX923;
+    use cryptopals_padding::iso7816::Iso7816;
+    use cryptopals_padding::iso10126::Iso10126;
+    use cryptopals_padding::pkcs7::Pkcs7;
+    use cryptopals_padding::zero::ZeroPadding;
+    use cryptopals_padding::{InvalidPaddingError, NoPadding};
+    use cryptopals_primitives::aes::Aes256;
+    use hybrid_array::sizes::U16;
+
     use super::*;
+    use crate::Error;
 
     #[test]
     fn basic() {}
+
+    /// CBC-AES256 (F.2.5 and F.2.6)
+    ///
+    /// Source: https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38a.pdf
+    #[test]
+    fn nist_sp800_38a_aes256() {
+        const KEY: [u8; 32] = [
+            0x60, 0x3d, 0xeb, 0x10, 0x15, 0xca, 0x71, 0xbe, 0x2b, 0x73, 0xae, 0xf0, 0x85, 0x7d,
+            0x77, 0x81, 0x1f, 0x35, 0x2c, 0x07, 0x3b, 0x61, 0x08, 0xd7, 0x2d, 0x98, 0x10, 0xa3,
+            0x09, 0x14, 0xdf, 0xf4,
+        ];
+        const IV: [u8; 16] = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0a, 0x0b, 0x0c, 0x0d,
+            0x0e, 0x0f,
+        ];
+        const PLAINTEXT: [u8; 64] = [
+            0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93,
+            0x17, 0x2a, 0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac,
+            0x45, 0xaf, 0x8e, 0x51, 0x30, 0xc8, 0x1c, 0x46, 0xa3, 0x5c, 0xe4, 0x11, 0xe5, 0xfb,
+            0xc1, 0x19, 0x1a, 0x0a, 0x52, 0xef, 0xf6, 0x9f, 0x24, 0x45, 0xdf, 0x4f, 0x9b, 0x17,
+            0xad, 0x2b, 0x41, 0x7b, 0xe6, 0x6c, 0x37, 0x10,
+        ];
+        const CIPHERTEXT: [u8; 64] = [
+            0xf5, 0x8c, 0x4c, 0x04, 0xd6, 0xe5, 0xf1, 0xba, 0x77, 0x9e, 0xab, 0xfb, 0x5f, 0x7b,
+            0xfb, 0xd6, 0x9c, 0xfc, 0x4e, 0x96, 0x7e, 0xdb, 0x80, 0x8d, 0x67, 0x9f, 0x77, 0x7b,
+            0xc6, 0x70, 0x2c, 0x7d, 0x39, 0xf2, 0x33, 0x69, 0xa9, 0xd9, 0xba, 0xcf, 0xa5, 0x30,
+            0xe2, 0x63, 0x04, 0x23, 0x14, 0x61, 0xb2, 0xeb, 0x05, 0xe2, 0xc3, 0x9b, 0xe9, 0xfc,
+            0xda, 0x6c, 0x19, 0x07, 0x8c, 0x6a, 0x9d, 0x1b,
+        ];
+
+        let aes = Aes256::new(KEY.into());
+        let mut cbc = Cbc::new(aes, IV.into());
+        let mut buffer = PLAINTEXT;
+        cbc.encrypt_padded::<NoPadding>(&mut buffer, 64);
+        assert_eq!(buffer, CIPHERTEXT);
+        cbc.decrypt(&mut buffer);
+        assert_eq!(buffer, PLAINTEXT);
+    }
+
+    fn padded_round_trip<P: Padding<U16>>() {
+        let aes = Aes256::new([0x42; 32].into());
+        let mut cbc = Cbc::new(aes, [0x13; 16].into());
+        let mut buffer = [0; 32];
+        buffer[..20].copy_from_slice(b"ICE ICE BABY ICE ICE");
+        cbc.encrypt_padded::<P>(&mut buffer, 20);
+        let plaintext = cbc.decrypt_padded::<P>(&mut buffer).unwrap();
+        assert_eq!(plaintext, b"ICE ICE BABY ICE ICE");
+    }
+
+    #[test]
+    fn interchangeable_padding() {
+        padded_round_trip::<Pkcs7<U16>>();
+        padded_round_trip::<AnsiX923<U16>>();
+        padded_round_trip::<Iso10126<U16>>();
+        padded_round_trip::<Iso7816<U16>>();
+        padded_round_trip::<ZeroPadding<U16>>();
+    }
+
+    #[test]
+    fn invalid_padding() {
+        let aes = Aes256::new([0x42; 32].into());
+        let mut cbc = Cbc::new(aes, [0; 16].into());
+        let mut buffer = *b"YELLOW SUBMARIN\x02";
+        cbc.encrypt_padded::<NoPadding>(&mut buffer, 16);
+        let result = cbc.decrypt_padded::<Pkcs7<U16>>(&mut buffer);
+        assert_eq!(
+            result,
+            Err(Error::Padding(InvalidPaddingError::MismatchingPaddingBytes))
+        );
+    }
 }
@@ -1,21 +1,202 @@
 //! # Counter (CTR) Mode
 //!
+//! Turns a block cipher into a stream cipher.
+//! The keystream is produced by encrypting successive counter blocks,
+//! and then XORed onto the data, so no padding is required.
+//!
+//! Encryption and decryption are the same operation, see [`Ctr::apply_keystream`].
+//!
 //! ## Usage
 
-use cryptopals_padding::{Padding, pkcs7::Pkcs7};
+use core::marker::PhantomData;
+
+use cryptopals_primitives::{BlockCipher, xor};
+use hybrid_array::sizes::U16;
+use hybrid_array::{Array, ArraySize};
+
+/// Describes how nonce and block counter are laid out within the counter block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CounterLayout {
+    /// 64-bit little-endian nonce, followed by a 64-bit little-endian block counter.
+    ///
+    /// This is the layout used by Cryptopals.
+    NonceLe64CounterLe64,
+    /// 96-bit nonce, followed by a 32-bit big-endian block counter.
+    ///
+    /// This is the layout used by GCM.
+    Nonce96CounterBe32,
+}
+
+impl CounterLayout {
+    /// Returns the counter block that is `blocks` blocks after `initial`.
+    ///
+    /// The counter wraps around within its field and never carries into the nonce.
+    pub fn counter_block(&self, initial: &Array<u8, U16>, blocks: u64) -> Array<u8, U16> {
+        let mut block = *initial;
+        match self {
+            Self::NonceLe64CounterLe64 => {
+                let counter = u64::from_le_bytes(block[8..].try_into().unwrap());
+                let counter = counter.wrapping_add(blocks);
+                block[8..].copy_from_slice(&counter.to_le_bytes());
+            }
+            Self::Nonce96CounterBe32 => {
+                let counter = u32::from_be_bytes(block[12..].try_into().unwrap());
+                let counter = counter.wrapping_add(blocks as u32);
+                block[12..].copy_from_slice(&counter.to_be_bytes());
+            }
+        }
+        block
+    }
+}
+
+/// CTR mode over a block cipher with 128-bit blocks and key size `K`.
+pub struct Ctr<C: BlockCipher<U16, K>, K: ArraySize> {
+    cipher: C,
+    initial_block: Array<u8, U16>,
+    layout: CounterLayout,
+    _key_size: PhantomData<K>,
+}
+
+impl<C: BlockCipher<U16, K>, K: ArraySize> Ctr<C, K> {
+    /// Creates a new CTR instance with the given initial counter block.
+    ///
+    /// The initial counter block contains both the nonce and the initial value of the counter,
+    /// with their positions determined by `layout`.
+    pub fn new(cipher: C, initial_block: Array<u8, U16>, layout: CounterLayout) -> Self {
+        Self {
+            cipher,
+            initial_block,
+            layout,
+            _key_size: PhantomData,
+        }
+    }
+
+    /// Creates a new CTR instance in the layout used by Cryptopals.
+    ///
+    /// The counter starts at zero.
+    pub fn with_nonce(cipher: C, nonce: u64) -> Self {
+        let mut initial_block = Array::<u8, U16>::default();
+        initial_block[..8].copy_from_slice(&nonce.to_le_bytes());
+        Self::new(cipher, initial_block, CounterLayout::NonceLe64CounterLe64)
+    }
+
+    /// Encrypts or decrypts `bytes` in place.
+    ///
+    /// The keystream always starts at the initial counter block.
+    /// `bytes` can be of any length, the final partial block uses a truncated keystream block.
+    pub fn apply_keystream(&mut self, bytes: &mut [u8]) {
+        self.apply_keystream_at(bytes, 0);
+    }
 
-// struct Ctr<C: BlockCipher, P: Padding = Pkcs7> {
-//     cipher: C,
-//     iv: Array<u8, C::BlockSize>,
-//     padding: P,
-// }
-//
-// impl Ctr {}
+    /// Encrypts or decrypts `bytes` in place, as if they were located at byte `offset` of the stream.
+    ///
+    /// Seeks directly to the counter block containing `offset`,
+    /// so none of the keystream before it is computed.
+    pub fn apply_keystream_at(&mut self, bytes: &mut [u8], offset: u64) {
+        let block_size = C::BLOCK_SIZE as u64;
+        let mut block_index = offset / block_size;
+        let mut skip = (offset % block_size) as usize;
+
+        let mut remaining = bytes;
+        while !remaining.is_empty() {
+            let mut keystream = self.layout.counter_block(&self.initial_block, block_index);
+            self.cipher.encrypt_block_in_place(&mut keystream);
+            let len = remaining.len().min(C::BLOCK_SIZE - skip);
+            let (chunk, rest) = remaining.split_at_mut(len);
+            xor::encrypt_fixed(chunk, &keystream[skip..skip + len]);
+            remaining = rest;
+            block_index += 1;
+            skip = 0;
+        }
+    }
+}
 
 #[cfg(test)]
 mod tests {
+    use cryptopals_primitives::aes::Aes128;
+
     use super::*;
 
+    /// Source: https://cryptopals.com/sets/3/challenges/18
+    #[test]
+    fn cryptopals() {
+        let aes = Aes128::new((*b"YELLOW SUBMARINE").into());
+        let mut ctr = Ctr::with_nonce(aes, 0);
+        let mut buffer = [
+            0x2f, 0xbe, 0xe7, 0x6b, 0xf9, 0xeb, 0x16, 0xc2, 0xaf, 0xca, 0x77, 0x7a, 0x1f, 0x33,
+            0xa8, 0x1b, 0xb1, 0x87, 0x4c, 0xb5, 0xec, 0x4d, 0x5b, 0xbd, 0xaa, 0xf6, 0x3f, 0xda,
+            0xcc, 0x8b, 0x5f, 0x38, 0x4f, 0xc1, 0xec, 0xb2, 0x31, 0x32, 0x54, 0x2e, 0xef, 0xfa,
+            0xfe, 0x45, 0xd7, 0xd0, 0xa4, 0xaf, 0xa0, 0xe2, 0xd2, 0x15,
+        ];
+        ctr.apply_keystream(&mut buffer);
+        assert_eq!(
+            &buffer,
+            b"Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby "
+        );
+    }
+
+    /// CTR-AES128 (F.5.1)
+    ///
+    /// Source: https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38a.pdf
+    #[test]
+    fn nist_sp800_38a() {
+        const KEY: [u8; 16] = [
+            0x2b, 0x7e, 0x15, 0x16, 0x28, 0xae, 0xd2, 0xa6, 0xab, 0xf7, 0x15, 0x88, 0x09, 0xcf,
+            0x4f, 0x3c,
+        ];
+        const INITIAL_BLOCK: [u8; 16] = [
+            0xf0, 0xf1, 0xf2, 0xf3, 0xf4, 0xf5, 0xf6, 0xf7, 0xf8, 0xf9, 0xfa, 0xfb, 0xfc, 0xfd,
+            0xfe, 0xff,
+        ];
+        const PLAINTEXT: [u8; 64] = [
+            0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93,
+            0x17, 0x2a, 0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac,
+            0x45, 0xaf, 0x8e, 0x51, 0x30, 0xc8, 0x1c, 0x46, 0xa3, 0x5c, 0xe4, 0x11, 0xe5, 0xfb,
+            0xc1, 0x19, 0x1a, 0x0a, 0x52, 0xef, 0xf6, 0x9f, 0x24, 0x45, 0xdf, 0x4f, 0x9b, 0x17,
+            0xad, 0x2b, 0x41, 0x7b, 0xe6, 0x6c, 0x37, 0x10,
+        ];
+        const CIPHERTEXT: [u8; 64] = [
+            0x87, 0x4d, 0x61, 0x91, 0xb6, 0x20, 0xe3, 0x26, 0x1b, 0xef, 0x68, 0x64, 0x99, 0x0d,
+            0xb6, 0xce, 0x98, 0x06, 0xf6, 0x6b, 0x79, 0x70, 0xfd, 0xff, 0x86, 0x17, 0x18, 0x7b,
+            0xb9, 0xff, 0xfd, 0xff, 0x5a, 0xe4, 0xdf, 0x3e, 0xdb, 0xd5, 0xd3, 0x5e, 0x5b, 0x4f,
+            0x09, 0x02, 0x0d, 0xb0, 0x3e, 0xab, 0x1e, 0x03, 0x1d, 0xda, 0x2f, 0xbe, 0x03, 0xd1,
+            0x79, 0x21, 0x70, 0xa0, 0xf3, 0x00, 0x9c, 0xee,
+        ];
+
+        let aes = Aes128::new(KEY.into());
+        let mut ctr = Ctr::new(aes, INITIAL_BLOCK.into(), CounterLayout::Nonce96CounterBe32);
+        let mut buffer = PLAINTEXT;
+        ctr.apply_keystream(&mut buffer);
+        assert_eq!(buffer, CIPHERTEXT);
+        ctr.apply_keystream(&mut buffer);
+        assert_eq!(buffer, PLAINTEXT);
+    }
+
+    #[test]
+    fn seek() {
+        let aes = Aes128::new([0x42; 16].into());
+        let mut ctr = Ctr::with_nonce(aes, 7);
+        let mut full = [0; 100];
+        ctr.apply_keystream(&mut full);
+
+        for offset in [0, 1, 15, 16, 17, 50, 99] {
+            for len in [0, 1, 16, 33] {
+                let len = len.min(100 - offset);
+                let mut partial = [0; 33];
+                ctr.apply_keystream_at(&mut partial[..len], offset as u64);
+                assert_eq!(partial[..len], full[offset..offset + len]);
+            }
+        }
+    }
+
     #[test]
-    fn basic() {}
+    fn counter_wraps_within_field() {
+        let initial = Array::<u8, U16>::from([0xff; 16]);
+        let le = CounterLayout::NonceLe64CounterLe64.counter_block(&initial, 1);
+        assert_eq!(&le[..8], &[0xff; 8]);
+        assert_eq!(&le[8..], &[0; 8]);
+        let be = CounterLayout::Nonce96CounterBe32.counter_block(&initial, 1);
+        assert_eq!(&be[..12], &[0xff; 12]);
+        assert_eq!(&be[12..], &[0; 4]);
+    }
 }
@@ -7,54 +7,137 @@
 //!
 //! ## Usage
 
+use core::marker::PhantomData;
+
 use cryptopals_padding::Padding;
 use cryptopals_primitives::BlockCipher;
-use hybrid_array::{Array, sizes::U16};
+use hybrid_array::ArraySize;
+
+use crate::{Result, check_block_aligned};
 
-// TODO: generalize over block size
-pub struct Ecb<C: BlockCipher<U16, U16>> {
+/// ECB mode over a block cipher with block size `B` and key size `K`.
+pub struct Ecb<C: BlockCipher<B, K>, B: ArraySize, K: ArraySize> {
     cipher: C,
+    _sizes: PhantomData<(B, K)>,
 }
 
-impl<C: BlockCipher<U16, U16>> Ecb<C> {
+impl<C: BlockCipher<B, K>, B: ArraySize, K: ArraySize> Ecb<C, B, K> {
     ///
     pub fn new(cipher: C) -> Self {
-        Self { cipher }
-    }
-
-    ///
-    pub fn encrypt(&mut self, buffer: &mut [u8], len: usize) {
-        todo!()
+        Self {
+            cipher,
+            _sizes: PhantomData,
+        }
     }
 
+    /// Encrypts `bytes` in place, without applying any padding.
     ///
-    pub fn encrypt_padded<P: Padding<U16>>(&mut self, bytes: &mut [u8], len: usize) {
-        assert!(bytes.len().is_multiple_of(C::BLOCK_SIZE));
-        P::pad_bytes(bytes, len);
+    /// Returns an error if `bytes` is not a multiple of the block size.
+    pub fn encrypt(&mut self, bytes: &mut [u8]) -> Result<()> {
+        check_block_aligned(bytes, C::BLOCK_SIZE)?;
         for chunk in bytes.chunks_exact_mut(C::BLOCK_SIZE) {
             self.cipher.encrypt_block_in_place(chunk);
         }
+        Ok(())
     }
 
+    /// Pads the first `len` bytes of `bytes` with `P` and encrypts the result in place.
     ///
-    pub fn decrypt(&mut self, bytes: &mut [u8], len: usize) {
-        todo!()
+    /// Returns an error if `bytes` is not a multiple of the block size.
+    pub fn encrypt_padded<P: Padding<B>>(&mut self, bytes: &mut [u8], len: usize) -> Result<()> {
+        check_block_aligned(bytes, C::BLOCK_SIZE)?;
+        P::pad_bytes(bytes, len);
+        self.encrypt(bytes)
     }
 
+    /// Decrypts `bytes` in place, without removing any padding.
     ///
-    pub fn decrypt_padded<'a, P: Padding<U16>>(&mut self, bytes: &'a mut [u8]) -> &'a [u8] {
+    /// Returns an error if `bytes` is not a multiple of the block size.
+    pub fn decrypt(&mut self, bytes: &mut [u8]) -> Result<()> {
+        check_block_aligned(bytes, C::BLOCK_SIZE)?;
         for chunk in bytes.chunks_exact_mut(C::BLOCK_SIZE) {
             self.cipher.decrypt_block_in_place(chunk);
         }
-        let unpadded_len = P::unpad_bytes(bytes).len();
-        &bytes[..unpadded_len]
+        Ok(())
+    }
+
+    /// Decrypts `bytes` in place and returns the plaintext with padding `P` removed.
+    ///
+    /// Returns an error if `bytes` is not a multiple of the block size,
+    /// or if the decrypted plaintext is not correctly padded.
+    pub fn decrypt_padded<'a, P: Padding<B>>(&mut self, bytes: &'a mut [u8]) -> Result<&'a [u8]> {
+        self.decrypt(bytes)?;
+        let unpadded_len = P::unpad_bytes(bytes)?.len();
+        Ok(&bytes[..unpadded_len])
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use cryptopals_padding::NoPadding;
+    use cryptopals_primitives::aes::Aes192;
+
     use super::*;
+    use crate::Error;
 
     #[test]
     fn basic() {}
+
+    /// ECB-AES192 (F.1.3 and F.1.4)
+    ///
+    /// Source: https://nvlpubs.nist.gov/nistpubs/Legacy/SP/nistspecialpublication800-38a.pdf
+    #[test]
+    fn nist_sp800_38a_aes192() {
+        const KEY: [u8; 24] = [
+            0x8e, 0x73, 0xb0, 0xf7, 0xda, 0x0e, 0x64, 0x52, 0xc8, 0x10, 0xf3, 0x2b, 0x80, 0x90,
+            0x79, 0xe5, 0x62, 0xf8, 0xea, 0xd2, 0x52, 0x2c, 0x6b, 0x7b,
+        ];
+        const PLAINTEXT: [u8; 64] = [
+            0x6b, 0xc1, 0xbe, 0xe2, 0x2e, 0x40, 0x9f, 0x96, 0xe9, 0x3d, 0x7e, 0x11, 0x73, 0x93,
+            0x17, 0x2a, 0xae, 0x2d, 0x8a, 0x57, 0x1e, 0x03, 0xac, 0x9c, 0x9e, 0xb7, 0x6f, 0xac,
+            0x45, 0xaf, 0x8e, 0x51, 0x30, 0xc8, 0x1c, 0x46, 0xa3, 0x5c, 0xe4, 0x11, 0xe5, 0xfb,
+            0xc1, 0x19, 0x1a, 0x0a, 0x52, 0xef, 0xf6, 0x9f, 0x24, 0x45, 0xdf, 0x4f, 0x9b, 0x17,
+            0xad, 0x2b, 0x41, 0x7b, 0xe6, 0x6c, 0x37, 0x10,
+        ];
+        const CIPHERTEXT: [u8; 64] = [
+            0xbd, 0x33, 0x4f, 0x1d, 0x6e, 0x45, 0xf2, 0x5f, 0xf7, 0x12, 0xa2, 0x14, 0x57, 0x1f,
+            0xa5, 0xcc, 0x97, 0x41, 0x04, 0x84, 0x6d, 0x0a, 0xd3, 0xad, 0x77, 0x34, 0xec, 0xb3,
+            0xec, 0xee, 0x4e, 0xef, 0xef, 0x7a, 0xfd, 0x22, 0x70, 0xe2, 0xe6, 0x0a, 0xdc, 0xe0,
+            0xba, 0x2f, 0xac, 0xe6, 0x44, 0x4e, 0x9a, 0x4b, 0x41, 0xba, 0x73, 0x8d, 0x6c, 0x72,
+            0xfb, 0x16, 0x69, 0x16, 0x03, 0xc1, 0x8e, 0x0e,
+        ];
+
+        let aes = Aes192::new(KEY.into());
+        let mut ecb = Ecb::new(aes);
+        let mut buffer = PLAINTEXT;
+        ecb.encrypt_padded::<NoPadding>(&mut buffer, 64).unwrap();
+        assert_eq!(buffer, CIPHERTEXT);
+        let plaintext = ecb.decrypt_padded::<NoPadding>(&mut buffer).unwrap();
+        assert_eq!(plaintext, PLAINTEXT);
+    }
+
+    #[test]
+    fn unpadded_round_trip() {
+        let aes = Aes192::new([0x42; 24].into());
+        let mut ecb = Ecb::new(aes);
+        let mut buffer = *b"YELLOW SUBMARINEYELLOW SUBMARINE";
+        ecb.encrypt(&mut buffer).unwrap();
+        assert_eq!(buffer[..16], buffer[16..]);
+        ecb.decrypt(&mut buffer).unwrap();
+        assert_eq!(&buffer, b"YELLOW SUBMARINEYELLOW SUBMARINE");
+    }
+
+    #[test]
+    fn not_block_aligned() {
+        let aes = Aes192::new([0x42; 24].into());
+        let mut ecb = Ecb::new(aes);
+        let mut buffer = *b"YELLOW SUBMARINE!";
+        let expected = Error::NotBlockAligned {
+            len: 17,
+            block_size: 16,
+        };
+        assert_eq!(ecb.encrypt(&mut buffer), Err(expected));
+        assert_eq!(ecb.decrypt(&mut buffer), Err(expected));
+        assert_eq!(&buffer, b"YELLOW SUBMARINE!");
+    }
 }
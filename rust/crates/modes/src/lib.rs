@@ -4,6 +4,33 @@
 
 #![no_std]
 
+use cryptopals_padding::InvalidPaddingError;
+use thiserror::Error;
+
+/// Errors that can occur when applying a mode of operation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum Error {
+    /// The input length is not a multiple of the block size.
+    #[error("input length {len} is not a multiple of the block size {block_size}")]
+    NotBlockAligned { len: usize, block_size: usize },
+    /// The decrypted plaintext is not correctly padded.
+    #[error("invalid padding: {0}")]
+    Padding(#[from] InvalidPaddingError),
+}
+
+pub type Result<T> = core::result::Result<T, Error>;
+
+/// Returns an error if `bytes` does not consist of full blocks.
+fn check_block_aligned(bytes: &[u8], block_size: usize) -> Result<()> {
+    if !bytes.len().is_multiple_of(block_size) {
+        return Err(Error::NotBlockAligned {
+            len: bytes.len(),
+            block_size,
+        });
+    }
+    Ok(())
+}
+
 pub mod cbc;
 pub mod ctr;
 pub mod ecb;
@@ -0,0 +1,59 @@
+//! ANSI X9.23 Padding
+//!
+//! Pads with zero bytes, followed by a final byte holding the number of padding bytes.
+//! For example, three bytes of padding are `00 00 03`.
+
+use hybrid_array::ArraySize;
+
+use super::{InvalidPaddingError, Padding, Result, trailing_length_byte};
+
+/// ANSI X9.23 padding for blocks of `N` bytes.
+pub struct AnsiX923<N: ArraySize>(core::marker::PhantomData<N>);
+
+impl<N: ArraySize> Padding<N> for AnsiX923<N> {
+    fn pad_bytes(data: &mut [u8], len: usize) {
+        assert!(len < data.len());
+        let padding_len = data.len() - len;
+        data[len..].fill(0);
+        data[data.len() - 1] = padding_len as u8;
+    }
+
+    fn unpad_bytes(data: &[u8]) -> Result<&[u8]> {
+        let padding_len = trailing_length_byte::<N>(data)?;
+        let fill = &data[data.len() - padding_len..data.len() - 1];
+        if fill.iter().any(|d| *d != 0) {
+            return Err(InvalidPaddingError::MismatchingPaddingBytes);
+        }
+        Ok(&data[..data.len() - padding_len])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hybrid_array::sizes::U16;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut buffer = [0xff; 16];
+        buffer[..12].copy_from_slice(b"ICE ICE BABY");
+        AnsiX923::<U16>::pad_bytes(&mut buffer, 12);
+        assert_eq!(&buffer, b"ICE ICE BABY\x00\x00\x00\x04");
+        let unpadded = AnsiX923::<U16>::unpad_bytes(&buffer).unwrap();
+        assert_eq!(unpadded, b"ICE ICE BABY");
+    }
+
+    #[test]
+    fn invalid_padding() {
+        use InvalidPaddingError::*;
+
+        let unpad = AnsiX923::<U16>::unpad_bytes;
+        assert_eq!(unpad(b"ICE ICE BABY\x00\x00\x00\x00"), Err(ZeroPadding));
+        assert_eq!(unpad(b"ICE ICE BABY\x00\x00\x00\x11"), Err(PaddingTooLarge));
+        assert_eq!(
+            unpad(b"ICE ICE BABY\x04\x04\x04\x04"),
+            Err(MismatchingPaddingBytes)
+        );
+    }
+}
@@ -0,0 +1,64 @@
+//! ISO 10126 Padding
+//!
+//! Pads with random bytes, followed by a final byte holding the number of padding bytes.
+//! Only the final byte carries information, so unpadding cannot check the other padding bytes.
+//! The random bytes are drawn from the operating system, which also works in `no_std` builds.
+//!
+//! The standard has been withdrawn, but it is still found in the wild.
+
+use hybrid_array::ArraySize;
+use rand_core::{OsRng, TryRngCore};
+
+use super::{Padding, Result, trailing_length_byte};
+
+/// ISO 10126 padding for blocks of `N` bytes.
+pub struct Iso10126<N: ArraySize>(core::marker::PhantomData<N>);
+
+impl<N: ArraySize> Padding<N> for Iso10126<N> {
+    fn pad_bytes(data: &mut [u8], len: usize) {
+        assert!(len < data.len());
+        let padding_len = data.len() - len;
+        let last = data.len() - 1;
+        // the OS generator works without std, unlike the thread-local one from `rand`
+        OsRng
+            .try_fill_bytes(&mut data[len..last])
+            .expect("OS random number generator failed");
+        data[last] = padding_len as u8;
+    }
+
+    fn unpad_bytes(data: &[u8]) -> Result<&[u8]> {
+        let padding_len = trailing_length_byte::<N>(data)?;
+        Ok(&data[..data.len() - padding_len])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hybrid_array::sizes::U16;
+
+    use super::*;
+    use crate::InvalidPaddingError;
+
+    #[test]
+    fn round_trip() {
+        let mut buffer = [0; 16];
+        buffer[..12].copy_from_slice(b"ICE ICE BABY");
+        Iso10126::<U16>::pad_bytes(&mut buffer, 12);
+        assert_eq!(buffer[15], 4);
+        let unpadded = Iso10126::<U16>::unpad_bytes(&buffer).unwrap();
+        assert_eq!(unpadded, b"ICE ICE BABY");
+    }
+
+    #[test]
+    fn invalid_padding() {
+        use InvalidPaddingError::*;
+
+        let unpad = Iso10126::<U16>::unpad_bytes;
+        assert_eq!(unpad(b"ICE ICE BABY\x2a\x2a\x2a\x00"), Err(ZeroPadding));
+        assert_eq!(unpad(b"ICE ICE BABY\x2a\x2a\x2a\x11"), Err(PaddingTooLarge));
+        assert_eq!(
+            unpad(b"ICE ICE BABY\x2a\x2a\x2a\x04"),
+            Ok(&b"ICE ICE BABY"[..])
+        );
+    }
+}
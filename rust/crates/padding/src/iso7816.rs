@@ -0,0 +1,73 @@
+//! ISO/IEC 7816-4 Padding
+//!
+//! Pads with a single `0x80` byte, followed by zero bytes.
+//! This is the same as bit padding, i.e. a single one bit followed by zero bits.
+//! For example, three bytes of padding are `80 00 00`.
+
+use hybrid_array::ArraySize;
+
+use super::{InvalidPaddingError, Padding, Result};
+
+/// ISO/IEC 7816-4 padding for blocks of `N` bytes.
+pub struct Iso7816<N: ArraySize>(core::marker::PhantomData<N>);
+
+impl<N: ArraySize> Padding<N> for Iso7816<N> {
+    fn pad_bytes(data: &mut [u8], len: usize) {
+        assert!(len < data.len());
+        data[len] = 0x80;
+        data[len + 1..].fill(0);
+    }
+
+    fn unpad_bytes(data: &[u8]) -> Result<&[u8]> {
+        if data.is_empty() || !data.len().is_multiple_of(N::USIZE) {
+            return Err(InvalidPaddingError::NotBlockAligned);
+        }
+        let Some(marker) = data.iter().rposition(|d| *d != 0) else {
+            return Err(InvalidPaddingError::MismatchingPaddingBytes);
+        };
+        if data.len() - marker > N::USIZE {
+            return Err(InvalidPaddingError::PaddingTooLarge);
+        }
+        if data[marker] != 0x80 {
+            return Err(InvalidPaddingError::MismatchingPaddingBytes);
+        }
+        Ok(&data[..marker])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hybrid_array::sizes::U16;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut buffer = [0xff; 16];
+        buffer[..12].copy_from_slice(b"ICE ICE BABY");
+        Iso7816::<U16>::pad_bytes(&mut buffer, 12);
+        assert_eq!(&buffer, b"ICE ICE BABY\x80\x00\x00\x00");
+        let unpadded = Iso7816::<U16>::unpad_bytes(&buffer).unwrap();
+        assert_eq!(unpadded, b"ICE ICE BABY");
+    }
+
+    #[test]
+    fn invalid_padding() {
+        use InvalidPaddingError::*;
+
+        let unpad = Iso7816::<U16>::unpad_bytes;
+        assert_eq!(unpad(&[0; 16]), Err(MismatchingPaddingBytes));
+        assert_eq!(
+            unpad(b"ICE ICE BABY\x00\x00\x00\x00"),
+            Err(MismatchingPaddingBytes)
+        );
+        assert_eq!(
+            unpad(b"ICE ICE BABY\x80\x00\x00\x01"),
+            Err(MismatchingPaddingBytes)
+        );
+
+        let mut buffer = [0; 32];
+        buffer[0] = 0x80;
+        assert_eq!(unpad(&buffer), Err(PaddingTooLarge));
+    }
+}
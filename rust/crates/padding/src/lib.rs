@@ -5,39 +5,86 @@
 //! ## Usage
 //!
 //! ```rust
-//! use hybrid_array::Array;
-//! use padding::{Padding, pkcs7::Pkcs7};
+//! use cryptopals_padding::{Padding, pkcs7::Pkcs7};
+//! use hybrid_array::sizes::U16;
 //!
-//! let data = b"YELLOW SUBMARINE";
-//! let padded = Pkcs7::pad(data);
-//! let unpadded = Pkcs7::unpad(&padded);
-//! assert_eq!(data, unpadded);
+//! let mut buffer = [0; 32];
+//! buffer[..16].copy_from_slice(b"YELLOW SUBMARINE");
+//! Pkcs7::<U16>::pad_bytes(&mut buffer, 16);
+//! let unpadded = Pkcs7::<U16>::unpad_bytes(&buffer).unwrap();
+//! assert_eq!(unpadded, b"YELLOW SUBMARINE");
 //! ```
 
 #![no_std]
 
 use hybrid_array::ArraySize;
-use hybrid_array::sizes::U16;
+use thiserror::Error;
 
-///
+/// Reasons for why unpadding can fail.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Error)]
+pub enum InvalidPaddingError {
+    /// The padded data is empty or not a multiple of the block size.
+    #[error("padded data is not a multiple of the block size")]
+    NotBlockAligned,
+    /// The padding claims to be zero bytes long.
+    #[error("padding length is zero")]
+    ZeroPadding,
+    /// The padding claims to be longer than a block.
+    #[error("padding length exceeds the block size")]
+    PaddingTooLarge,
+    /// The padding bytes are inconsistent with each other.
+    #[error("padding bytes do not match")]
+    MismatchingPaddingBytes,
+}
+
+pub type Result<T> = core::result::Result<T, InvalidPaddingError>;
+
+/// Padding scheme for blocks of `N` bytes.
 pub trait Padding<N: ArraySize> {
+    /// Pads the first `len` bytes of `data` in place, filling up the rest of `data`.
     fn pad_bytes(data: &mut [u8], len: usize);
-    fn unpad_bytes(data: &[u8]) -> &[u8];
+    /// Returns `data` with the padding removed.
+    ///
+    /// Returns an error if the padding is malformed.
+    fn unpad_bytes(data: &[u8]) -> Result<&[u8]>;
     // fn pad_block(data: &[u8]) -> Array<u8, N>;
     // fn unpad_block(data: &Array<u8, N>) -> &[u8];
 }
 
 pub struct NoPadding;
 
-impl Padding<U16> for NoPadding {
+impl<N: ArraySize> Padding<N> for NoPadding {
     fn pad_bytes(data: &mut [u8], _len: usize) {
-        assert!(data.len().is_multiple_of(16));
+        assert!(data.len().is_multiple_of(N::USIZE));
+    }
+
+    fn unpad_bytes(data: &[u8]) -> Result<&[u8]> {
+        if !data.len().is_multiple_of(N::USIZE) {
+            return Err(InvalidPaddingError::NotBlockAligned);
+        }
+        Ok(data)
     }
+}
 
-    fn unpad_bytes(data: &[u8]) -> &[u8] {
-        assert!(data.len().is_multiple_of(16));
-        data
+/// Reads the padding length from the last byte of `data`.
+///
+/// Used by all schemes that end in a length byte (PKCS #7, ANSI X9.23, ISO 10126).
+fn trailing_length_byte<N: ArraySize>(data: &[u8]) -> Result<usize> {
+    if data.is_empty() || !data.len().is_multiple_of(N::USIZE) {
+        return Err(InvalidPaddingError::NotBlockAligned);
+    }
+    let padding_len = usize::from(data[data.len() - 1]);
+    if padding_len == 0 {
+        return Err(InvalidPaddingError::ZeroPadding);
+    }
+    if padding_len > N::USIZE {
+        return Err(InvalidPaddingError::PaddingTooLarge);
     }
+    Ok(padding_len)
 }
 
+pub mod ansi_x923;
+pub mod iso10126;
+pub mod iso7816;
 pub mod pkcs7;
+pub mod zero;
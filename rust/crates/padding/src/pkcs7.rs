@@ -8,32 +8,55 @@
 //!
 //! [RFC 2315]: https://www.rfc-editor.org/rfc/rfc2315
 
-use hybrid_array::{Array, ArraySize};
+use hybrid_array::ArraySize;
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq, ConstantTimeGreater};
 
-use super::Padding;
+use super::{InvalidPaddingError, Padding, Result, trailing_length_byte};
 
-pub enum InvalidPaddingError {
-    PaddingTooLarge,
-    MismatchingPaddingBytes,
-}
-pub type Result<T> = core::result::Result<T, InvalidPaddingError>;
-
-///
+/// PKCS #7 padding for blocks of `N` bytes.
 pub struct Pkcs7<N: ArraySize>(core::marker::PhantomData<N>);
 
 impl<N: ArraySize> Pkcs7<N> {
+    /// Removes the padding from `data`, checking every padding byte.
+    ///
+    /// Returns early on the first invalid byte, so this is not constant-time.
     pub fn unpad_checked(data: &[u8]) -> Result<&[u8]> {
-        assert!(data.len().is_multiple_of(N::USIZE));
-        let padding_byte = data[data.len() - 1];
-        if padding_byte > N::USIZE as u8 {
-            return Err(InvalidPaddingError::PaddingTooLarge);
-        }
-        for d in &data[data.len() - padding_byte as usize..] {
+        let padding_len = trailing_length_byte::<N>(data)?;
+        let padding_byte = padding_len as u8;
+        for d in &data[data.len() - padding_len..] {
             if *d != padding_byte {
                 return Err(InvalidPaddingError::MismatchingPaddingBytes);
             }
         }
-        Ok(&data[..data.len() - padding_byte as usize])
+        Ok(&data[..data.len() - padding_len])
+    }
+
+    /// Checks the padding of `data` in constant time.
+    ///
+    /// Every byte of the final block is inspected and the result is accumulated
+    /// into a mask without branching, so the running time does not depend on the padding.
+    /// Only the length of `data` is treated as public.
+    ///
+    /// Returns whether the padding is valid, and the length of the unpadded data.
+    /// If the padding is invalid, the returned length is `data.len()`.
+    ///
+    /// Panics if `data` is empty or not a multiple of the block size.
+    pub fn unpad_constant_time(data: &[u8]) -> (Choice, usize) {
+        assert!(!data.is_empty() && data.len().is_multiple_of(N::USIZE));
+        let block = &data[data.len() - N::USIZE..];
+        let padding_byte = block[N::USIZE - 1];
+        let padding_len = u64::from(padding_byte);
+
+        let mut valid = !padding_byte.ct_eq(&0) & !padding_len.ct_gt(&(N::USIZE as u64));
+        for (i, byte) in block.iter().enumerate() {
+            // 1-based position of this byte, counted from the end of the block
+            let position = (N::USIZE - i) as u64;
+            let in_padding = !position.ct_gt(&padding_len);
+            valid &= !in_padding | byte.ct_eq(&padding_byte);
+        }
+
+        let padding_len = u64::conditional_select(&0, &padding_len, valid);
+        (valid, data.len() - padding_len as usize)
     }
 }
 
@@ -44,17 +67,15 @@ impl<N: ArraySize> Padding<N> for Pkcs7<N> {
         data[len..].fill(padding_byte);
     }
 
-    fn unpad_bytes(data: &[u8]) -> &[u8] {
-        assert!(data.len().is_multiple_of(N::USIZE));
-        let padding_byte = data[data.len() - 1];
-        assert!(padding_byte < N::USIZE as u8);
-        &data[..data.len() - padding_byte as usize]
+    fn unpad_bytes(data: &[u8]) -> Result<&[u8]> {
+        Self::unpad_checked(data)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use hybrid_array::sizes::U20;
+    use hybrid_array::sizes::{U16, U20};
+    use rand::{Rng, RngCore};
 
     use super::*;
 
@@ -64,7 +85,64 @@ mod tests {
         buffer[0..16].copy_from_slice(b"YELLOW SUBMARINE");
         Pkcs7::<U20>::pad_bytes(&mut buffer, 16);
         assert_eq!(&buffer, b"YELLOW SUBMARINE\x04\x04\x04\x04");
-        let unpadded = Pkcs7::<U20>::unpad_bytes(&buffer);
+        let unpadded = Pkcs7::<U20>::unpad_bytes(&buffer).unwrap();
         assert_eq!(&unpadded, b"YELLOW SUBMARINE");
     }
+
+    #[test]
+    fn full_block_of_padding() {
+        let mut buffer = [0; 32];
+        buffer[..16].copy_from_slice(b"YELLOW SUBMARINE");
+        Pkcs7::<U16>::pad_bytes(&mut buffer, 16);
+        assert_eq!(&buffer[16..], &[0x10; 16]);
+        let unpadded = Pkcs7::<U16>::unpad_bytes(&buffer).unwrap();
+        assert_eq!(unpadded, b"YELLOW SUBMARINE");
+    }
+
+    #[test]
+    fn constant_time_matches_checked() {
+        let mut rng = rand::rng();
+        let mut block = [0; 16];
+        for padding_byte in 0..=255 {
+            for fill in [padding_byte, padding_byte ^ 1, rng.random()] {
+                rng.fill_bytes(&mut block);
+                let padding_len = usize::from(padding_byte).min(16);
+                block[16 - padding_len..].fill(padding_byte);
+                // possibly corrupt one byte within the claimed padding
+                block[15 - padding_len.min(15) / 2] = fill;
+                block[15] = padding_byte;
+
+                let (valid, len) = Pkcs7::<U16>::unpad_constant_time(&block);
+                match Pkcs7::<U16>::unpad_checked(&block) {
+                    Ok(unpadded) => {
+                        assert!(bool::from(valid));
+                        assert_eq!(len, unpadded.len());
+                    }
+                    Err(_) => {
+                        assert!(!bool::from(valid));
+                        assert_eq!(len, 16);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_padding() {
+        use InvalidPaddingError::*;
+
+        let unpad = Pkcs7::<U16>::unpad_bytes;
+        assert_eq!(unpad(b"YELLOW SUBMARINE!"), Err(NotBlockAligned));
+        assert_eq!(unpad(b""), Err(NotBlockAligned));
+        assert_eq!(unpad(b"YELLOW SUBMARIN\x00"), Err(ZeroPadding));
+        assert_eq!(unpad(b"YELLOW SUBMARIN\x11"), Err(PaddingTooLarge));
+        assert_eq!(
+            unpad(b"ICE ICE BABY\x05\x05\x05\x05"),
+            Err(MismatchingPaddingBytes)
+        );
+        assert_eq!(
+            unpad(b"ICE ICE BABY\x01\x02\x03\x04"),
+            Err(MismatchingPaddingBytes)
+        );
+    }
 }
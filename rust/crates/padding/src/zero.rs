@@ -0,0 +1,61 @@
+//! Zero Padding
+//!
+//! Pads with zero bytes only.
+//! This is ambiguous if the data itself can end in zero bytes,
+//! since those are removed along with the padding.
+
+use hybrid_array::ArraySize;
+
+use super::{InvalidPaddingError, Padding, Result};
+
+/// Zero padding for blocks of `N` bytes.
+pub struct ZeroPadding<N: ArraySize>(core::marker::PhantomData<N>);
+
+impl<N: ArraySize> Padding<N> for ZeroPadding<N> {
+    fn pad_bytes(data: &mut [u8], len: usize) {
+        assert!(len <= data.len());
+        data[len..].fill(0);
+    }
+
+    /// Removes up to `N` trailing zero bytes.
+    fn unpad_bytes(data: &[u8]) -> Result<&[u8]> {
+        if !data.len().is_multiple_of(N::USIZE) {
+            return Err(InvalidPaddingError::NotBlockAligned);
+        }
+        let min_len = data.len().saturating_sub(N::USIZE);
+        let len = data[min_len..]
+            .iter()
+            .rposition(|d| *d != 0)
+            .map_or(min_len, |i| min_len + i + 1);
+        Ok(&data[..len])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use hybrid_array::sizes::U16;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut buffer = [0xff; 16];
+        buffer[..12].copy_from_slice(b"ICE ICE BABY");
+        ZeroPadding::<U16>::pad_bytes(&mut buffer, 12);
+        assert_eq!(&buffer, b"ICE ICE BABY\x00\x00\x00\x00");
+        let unpadded = ZeroPadding::<U16>::unpad_bytes(&buffer).unwrap();
+        assert_eq!(unpadded, b"ICE ICE BABY");
+    }
+
+    #[test]
+    fn strips_at_most_one_block() {
+        let unpad = ZeroPadding::<U16>::unpad_bytes;
+        assert_eq!(unpad(&[0; 32]), Ok(&[0; 16][..]));
+        assert_eq!(unpad(b"YELLOW SUBMARINE"), Ok(&b"YELLOW SUBMARINE"[..]));
+        assert_eq!(unpad(b"YELLOW SUBMARIN\x00"), Ok(&b"YELLOW SUBMARIN"[..]));
+        assert_eq!(
+            unpad(b"YELLOW SUBMARINE!"),
+            Err(InvalidPaddingError::NotBlockAligned)
+        );
+    }
+}
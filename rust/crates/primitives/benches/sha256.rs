@@ -0,0 +1,30 @@
+use std::hint::black_box;
+
+use criterion::{Criterion, Throughput, criterion_group, criterion_main};
+use cryptopals_primitives::sha256::{Sha224, Sha256};
+
+fn criterion_benchmark(c: &mut Criterion) {
+    const MESSAGE: [u8; 1024] = [0x5a; 1024];
+
+    let mut group = c.benchmark_group("SHA-2");
+    group.throughput(Throughput::Bytes(MESSAGE.len() as u64));
+
+    group.bench_function("SHA-256 1 KiB", |b| {
+        b.iter(|| {
+            let digest = Sha256::digest(black_box(&MESSAGE));
+            black_box(digest);
+        })
+    });
+
+    group.bench_function("SHA-224 1 KiB", |b| {
+        b.iter(|| {
+            let digest = Sha224::digest(black_box(&MESSAGE));
+            black_box(digest);
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,193 @@
+//! HMAC
+//!
+//! This is an implementation of HMAC, as specified in [RFC 2104],
+//! generic over every [`MerkleDamgard`] hash function in this crate.
+//!
+//! Unlike the secret-prefix MAC `H(key || message)`, HMAC is not vulnerable to length extension.
+//! Tags should be checked with [`Hmac::verify`], which runs in constant time.
+//! [`insecure_compare`] only exists to demonstrate timing attacks.
+//!
+//! [RFC 2104]: https://www.rfc-editor.org/rfc/rfc2104
+
+use hybrid_array::Array;
+use subtle::ConstantTimeEq;
+
+use crate::merkle_damgard::{Hasher, MerkleDamgard};
+
+/// Incremental HMAC over hash function `H`.
+#[derive(Clone, Debug)]
+pub struct Hmac<H: MerkleDamgard> {
+    inner: Hasher<H>,
+    outer_key: Array<u8, H::BlockSize>,
+}
+
+impl<H: MerkleDamgard> Hmac<H> {
+    const IPAD: u8 = 0x36;
+    const OPAD: u8 = 0x5c;
+
+    /// Creates a new HMAC instance for `key`.
+    ///
+    /// Keys longer than the block size are hashed first.
+    pub fn new(key: &[u8]) -> Self {
+        let mut block_key = Array::<u8, H::BlockSize>::default();
+        if key.len() > H::BLOCK_SIZE {
+            block_key[..H::OUTPUT_SIZE].copy_from_slice(&Hasher::<H>::digest(key));
+        } else {
+            block_key[..key.len()].copy_from_slice(key);
+        }
+
+        let mut inner_key = block_key.clone();
+        inner_key.iter_mut().for_each(|b| *b ^= Self::IPAD);
+        let mut inner = Hasher::<H>::new();
+        inner.update(&inner_key);
+
+        let mut outer_key = block_key;
+        outer_key.iter_mut().for_each(|b| *b ^= Self::OPAD);
+
+        Self { inner, outer_key }
+    }
+
+    /// Computes the HMAC of `message` under `key` in one go.
+    pub fn mac(key: &[u8], message: &[u8]) -> Array<u8, H::OutputSize> {
+        let mut hmac = Self::new(key);
+        hmac.update(message);
+        hmac.finalize()
+    }
+
+    /// Feeds `data` into the MAC.
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    /// Returns the tag of all data fed in so far.
+    pub fn finalize(self) -> Array<u8, H::OutputSize> {
+        let inner_digest = self.inner.finalize();
+        let mut outer = Hasher::<H>::new();
+        outer.update(&self.outer_key);
+        outer.update(&inner_digest);
+        outer.finalize()
+    }
+
+    /// Checks in constant time whether `tag` is the correct tag of all data fed in so far.
+    pub fn verify(self, tag: &[u8]) -> bool {
+        self.finalize().as_slice().ct_eq(tag).into()
+    }
+}
+
+/// Compares `a` and `b` byte by byte, returning as soon as a mismatch is found.
+///
+/// `per_byte_delay` is invoked after every matching byte, e.g. to sleep for a few milliseconds.
+/// This leaks the length of the common prefix through timing, NEVER use this for real tags.
+pub fn insecure_compare(a: &[u8], b: &[u8], mut per_byte_delay: impl FnMut()) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    for (x, y) in a.iter().zip(b) {
+        if x != y {
+            return false;
+        }
+        per_byte_delay();
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_utils::hex;
+
+    use super::*;
+    use crate::sha1::Sha1Core;
+    use crate::sha256::Sha256Core;
+
+    const LARGE_KEY_MESSAGE: &[u8] = b"Test Using Larger Than Block-Size Key - Hash Key First";
+
+    /// Source: https://www.rfc-editor.org/rfc/rfc2202 (Section 3)
+    #[test]
+    fn rfc2202_hmac_sha1() {
+        let vectors: [(&[u8], &[u8], &str); 4] = [
+            (
+                &[0x0b; 20],
+                b"Hi There",
+                "b617318655057264e28bc0b6fb378c8ef146be00",
+            ),
+            (
+                b"Jefe",
+                b"what do ya want for nothing?",
+                "effcdf6ae5eb2fa2d27416d5f184df9c259a7c79",
+            ),
+            (
+                &[0xaa; 20],
+                &[0xdd; 50],
+                "125d7342b9ac11cd91a39af48aa17b4f63f175d3",
+            ),
+            (
+                &[0xaa; 80],
+                LARGE_KEY_MESSAGE,
+                "aa4ae5e15272d00e95705637ce8a3b55ed402112",
+            ),
+        ];
+        for (key, message, expected) in vectors {
+            assert_eq!(hex::encode(&Hmac::<Sha1Core>::mac(key, message)), expected);
+        }
+    }
+
+    /// Source: https://www.rfc-editor.org/rfc/rfc4231 (Section 4)
+    #[test]
+    fn rfc4231_hmac_sha256() {
+        let vectors: [(&[u8], &[u8], &str); 4] = [
+            (
+                &[0x0b; 20],
+                b"Hi There",
+                "b0344c61d8db38535ca8afceaf0bf12b881dc200c9833da726e9376c2e32cff7",
+            ),
+            (
+                b"Jefe",
+                b"what do ya want for nothing?",
+                "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+            ),
+            (
+                &[0xaa; 20],
+                &[0xdd; 50],
+                "773ea91e36800e46854db8ebd09181a72959098b3ef8c122d9635514ced565fe",
+            ),
+            (
+                &[0xaa; 131],
+                LARGE_KEY_MESSAGE,
+                "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54",
+            ),
+        ];
+        for (key, message, expected) in vectors {
+            assert_eq!(
+                hex::encode(&Hmac::<Sha256Core>::mac(key, message)),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn incremental() {
+        let mut hmac = Hmac::<Sha256Core>::new(b"Jefe");
+        hmac.update(b"what do ya ");
+        hmac.update(b"want for nothing?");
+        let tag = Hmac::<Sha256Core>::mac(b"Jefe", b"what do ya want for nothing?");
+        assert_eq!(hmac.clone().finalize(), tag);
+
+        assert!(hmac.clone().verify(&tag));
+        assert!(!hmac.clone().verify(&tag[..31]));
+        let mut wrong_tag = tag;
+        wrong_tag[31] ^= 1;
+        assert!(!hmac.verify(&wrong_tag));
+    }
+
+    #[test]
+    fn insecure_compare_delays_per_matching_byte() {
+        let mut delays = 0;
+        assert!(!insecure_compare(b"abcd", b"abxd", || delays += 1));
+        assert_eq!(delays, 2);
+
+        let mut delays = 0;
+        assert!(insecure_compare(b"abcd", b"abcd", || delays += 1));
+        assert_eq!(delays, 4);
+        assert!(!insecure_compare(b"abcd", b"abc", || {}));
+    }
+}
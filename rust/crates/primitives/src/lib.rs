@@ -19,4 +19,10 @@ pub trait BlockCipher<B: ArraySize, K: ArraySize> {
 }
 
 pub mod aes;
+pub mod hmac;
+pub mod md4;
+pub mod merkle_damgard;
+pub mod mt19937;
+pub mod sha1;
+pub mod sha256;
 pub mod xor;
@@ -0,0 +1,145 @@
+//! MD4
+//!
+//! This is an implementation of the MD4 hash function, as specified in [RFC 1320].
+//!
+//! Like all hashes in this crate, it is built on the generic [`Hasher`],
+//! so its internal state can be read and injected, e.g. for length extension attacks.
+//! MD4 is completely broken, collisions can be found by hand (Wang et al.).
+//!
+//! [RFC 1320]: https://www.rfc-editor.org/rfc/rfc1320
+
+use hybrid_array::Array;
+use hybrid_array::sizes::{U16, U64};
+
+use crate::merkle_damgard::{Hasher, LengthEncoding, MerkleDamgard};
+
+/// Incremental MD4 hasher.
+pub type Md4 = Hasher<Md4Core>;
+
+/// MD4 compression function and parameters.
+#[derive(Clone, Debug)]
+pub struct Md4Core;
+
+impl MerkleDamgard for Md4Core {
+    type BlockSize = U64;
+    type OutputSize = U16;
+    type State = [u32; 4];
+
+    const INITIAL_STATE: [u32; 4] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476];
+    const LENGTH_ENCODING: LengthEncoding = LengthEncoding::LittleEndian;
+
+    fn compress(state: &mut [u32; 4], block: &Array<u8, U64>) {
+        compress(state, block);
+    }
+
+    fn state_to_digest(state: &[u32; 4]) -> Array<u8, U16> {
+        let mut digest = Array::<u8, U16>::default();
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+            bytes.copy_from_slice(&word.to_le_bytes());
+        }
+        digest
+    }
+
+    fn state_from_digest(digest: &Array<u8, U16>) -> [u32; 4] {
+        let mut state = [0; 4];
+        for (word, bytes) in state.iter_mut().zip(digest.chunks_exact(4)) {
+            *word = u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+        state
+    }
+}
+
+/// Applies the MD4 compression function to `state` with a single 512-bit `block`.
+fn compress(state: &mut [u32; 4], block: &[u8]) {
+    let mut x = [0u32; 16];
+    for (word, bytes) in x.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_le_bytes(bytes.try_into().unwrap());
+    }
+
+    let [mut a, mut b, mut c, mut d] = *state;
+
+    // round 1
+    for i in [0, 4, 8, 12] {
+        a = ff(a, b, c, d, x[i], 3);
+        d = ff(d, a, b, c, x[i + 1], 7);
+        c = ff(c, d, a, b, x[i + 2], 11);
+        b = ff(b, c, d, a, x[i + 3], 19);
+    }
+
+    // round 2
+    for i in [0, 1, 2, 3] {
+        a = gg(a, b, c, d, x[i], 3);
+        d = gg(d, a, b, c, x[i + 4], 5);
+        c = gg(c, d, a, b, x[i + 8], 9);
+        b = gg(b, c, d, a, x[i + 12], 13);
+    }
+
+    // round 3
+    for i in [0, 2, 1, 3] {
+        a = hh(a, b, c, d, x[i], 3);
+        d = hh(d, a, b, c, x[i + 8], 9);
+        c = hh(c, d, a, b, x[i + 4], 11);
+        b = hh(b, c, d, a, x[i + 12], 15);
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+/// Round 1 operation `a = (a + F(b,c,d) + x) <<< s`.
+fn ff(a: u32, b: u32, c: u32, d: u32, x: u32, s: u32) -> u32 {
+    let f = (b & c) | (!b & d);
+    a.wrapping_add(f).wrapping_add(x).rotate_left(s)
+}
+
+/// Round 2 operation `a = (a + G(b,c,d) + x + 5A827999) <<< s`.
+fn gg(a: u32, b: u32, c: u32, d: u32, x: u32, s: u32) -> u32 {
+    let g = (b & c) | (b & d) | (c & d);
+    a.wrapping_add(g)
+        .wrapping_add(x)
+        .wrapping_add(0x5a827999)
+        .rotate_left(s)
+}
+
+/// Round 3 operation `a = (a + H(b,c,d) + x + 6ED9EBA1) <<< s`.
+fn hh(a: u32, b: u32, c: u32, d: u32, x: u32, s: u32) -> u32 {
+    let h = b ^ c ^ d;
+    a.wrapping_add(h)
+        .wrapping_add(x)
+        .wrapping_add(0x6ed9eba1)
+        .rotate_left(s)
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_utils::hex;
+
+    use super::*;
+
+    /// Source: https://www.rfc-editor.org/rfc/rfc1320 (Appendix A.5)
+    #[test]
+    fn rfc1320() {
+        let vectors: [(&[u8], &str); 7] = [
+            (b"", "31d6cfe0d16ae931b73c59d7e0c089c0"),
+            (b"a", "bde52cb31de33e46245e05fbdbd6fb24"),
+            (b"abc", "a448017aaf21d8525fc10ae87aa6729d"),
+            (b"message digest", "d9130a8164549fe818874806e1c7014b"),
+            (
+                b"abcdefghijklmnopqrstuvwxyz",
+                "d79e1c308aa5bbcdeea8ed63df412da9",
+            ),
+            (
+                b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789",
+                "043f8582f241db351ce627e153e7f0e4",
+            ),
+            (
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "e33b4ddc9c38f2199c3e7b164fcc0536",
+            ),
+        ];
+        for (message, expected) in vectors {
+            assert_eq!(hex::encode(&Md4::digest(message)), expected);
+        }
+    }
+}
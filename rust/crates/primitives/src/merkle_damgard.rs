@@ -0,0 +1,209 @@
+//! Merkle–Damgård Construction
+//!
+//! Most classic hash functions (MD4, SHA-1, SHA-2) iterate a compression function over
+//! fixed-size blocks and append MD-strengthening padding, which encodes the message length.
+//! They only differ in compression function, state, block size and endianness of the length.
+//!
+//! [`MerkleDamgard`] captures exactly these differences,
+//! while [`Hasher`] implements buffering, padding and state injection once for all of them.
+//! Since the digest is the full final state, every such hash allows length extension.
+
+use core::marker::PhantomData;
+
+use hybrid_array::typenum::Unsigned;
+use hybrid_array::{Array, ArraySize};
+
+/// Byte order used to encode the message length in the final padding.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LengthEncoding {
+    /// 64-bit big-endian bit count, used by SHA-1 and SHA-2.
+    BigEndian,
+    /// 64-bit little-endian bit count, used by MD4 and MD5.
+    LittleEndian,
+}
+
+/// Core of a Merkle–Damgård hash function with block size `BlockSize` and digest size `OutputSize`.
+pub trait MerkleDamgard {
+    type BlockSize: ArraySize;
+    type OutputSize: ArraySize;
+    type State: Clone + Copy + core::fmt::Debug;
+
+    const BLOCK_SIZE: usize = Self::BlockSize::USIZE;
+    const OUTPUT_SIZE: usize = Self::OutputSize::USIZE;
+    const INITIAL_STATE: Self::State;
+    const LENGTH_ENCODING: LengthEncoding;
+
+    /// Applies the compression function to `state` with a single `block`.
+    fn compress(state: &mut Self::State, block: &Array<u8, Self::BlockSize>);
+
+    /// Serializes the final state into the digest.
+    fn state_to_digest(state: &Self::State) -> Array<u8, Self::OutputSize>;
+
+    /// Recovers the state that `digest` was produced from.
+    ///
+    /// Only fully possible for hashes that output their full state,
+    /// truncated ones like SHA-224 leave the missing words zeroed.
+    fn state_from_digest(digest: &Array<u8, Self::OutputSize>) -> Self::State;
+
+    /// Returns the length in bytes of the padding appended to a message of `message_len` bytes.
+    fn padding_len(message_len: u64) -> usize {
+        let block_size = Self::BLOCK_SIZE as u64;
+        let zeros = (2 * block_size - 1 - 8 - message_len % block_size) % block_size;
+        1 + zeros as usize + 8
+    }
+
+    /// Writes the padding for a message of `message_len` bytes into `out`.
+    ///
+    /// This is the "glue padding" needed for length extension.
+    /// `out` has to be exactly [`Self::padding_len`] bytes long.
+    fn write_padding(message_len: u64, out: &mut [u8]) {
+        assert_eq!(out.len(), Self::padding_len(message_len));
+        let bit_length = message_len.wrapping_mul(8);
+        let (marker, length) = out.split_at_mut(out.len() - 8);
+        marker[0] = 0x80;
+        marker[1..].fill(0);
+        length.copy_from_slice(&match Self::LENGTH_ENCODING {
+            LengthEncoding::BigEndian => bit_length.to_be_bytes(),
+            LengthEncoding::LittleEndian => bit_length.to_le_bytes(),
+        });
+    }
+}
+
+/// Incremental hasher for any [`MerkleDamgard`] hash function `H`.
+#[derive(Clone, Debug)]
+pub struct Hasher<H: MerkleDamgard> {
+    state: H::State,
+    buffer: Array<u8, H::BlockSize>,
+    buffer_len: usize,
+    length: u64,
+    _hash: PhantomData<H>,
+}
+
+impl<H: MerkleDamgard> Hasher<H> {
+    /// Creates a new hasher with the standard initial state.
+    pub fn new() -> Self {
+        Self::from_state(H::INITIAL_STATE, 0)
+    }
+
+    /// Creates a hasher from an arbitrary internal state.
+    ///
+    /// `processed_len` is the number of bytes that were already processed to reach `state`,
+    /// it has to be a multiple of the block size and is used for the final length padding.
+    pub fn from_state(state: H::State, processed_len: u64) -> Self {
+        assert!(processed_len.is_multiple_of(H::BLOCK_SIZE as u64));
+        Self {
+            state,
+            buffer: Array::default(),
+            buffer_len: 0,
+            length: processed_len,
+            _hash: PhantomData,
+        }
+    }
+
+    /// Computes the digest of `data` in one go.
+    pub fn digest(data: &[u8]) -> Array<u8, H::OutputSize> {
+        let mut hasher = Self::new();
+        hasher.update(data);
+        hasher.finalize()
+    }
+
+    /// Returns the current internal state, not including any buffered partial block.
+    pub fn state(&self) -> H::State {
+        self.state
+    }
+
+    /// Feeds `data` into the hasher.
+    pub fn update(&mut self, mut data: &[u8]) {
+        self.length = self.length.wrapping_add(data.len() as u64);
+
+        // fill up partial block first
+        if self.buffer_len > 0 {
+            let len = data.len().min(H::BLOCK_SIZE - self.buffer_len);
+            self.buffer[self.buffer_len..self.buffer_len + len].copy_from_slice(&data[..len]);
+            self.buffer_len += len;
+            data = &data[len..];
+            if self.buffer_len < H::BLOCK_SIZE {
+                return;
+            }
+            H::compress(&mut self.state, &self.buffer);
+            self.buffer_len = 0;
+        }
+
+        // process full blocks directly from input
+        let mut blocks = data.chunks_exact(H::BLOCK_SIZE);
+        for block in &mut blocks {
+            H::compress(&mut self.state, block.try_into().unwrap());
+        }
+        let rest = blocks.remainder();
+        self.buffer[..rest.len()].copy_from_slice(rest);
+        self.buffer_len = rest.len();
+    }
+
+    /// Applies the final padding and returns the digest.
+    pub fn finalize(mut self) -> Array<u8, H::OutputSize> {
+        // padding is at most one block plus the length field, blocks are at most 128 bytes
+        let mut padding = [0; 128 + 8];
+        let padding = &mut padding[..H::padding_len(self.length)];
+        H::write_padding(self.length, padding);
+        self.update(padding);
+        debug_assert_eq!(self.buffer_len, 0);
+        H::state_to_digest(&self.state)
+    }
+}
+
+impl<H: MerkleDamgard> Default for Hasher<H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::md4::Md4Core;
+    use crate::sha1::Sha1Core;
+    use crate::sha256::Sha256Core;
+
+    #[test]
+    fn padding_fills_last_block() {
+        for message_len in 0..200 {
+            let padding_len = Sha1Core::padding_len(message_len);
+            assert!((9..=64 + 8).contains(&padding_len));
+            assert_eq!((message_len as usize + padding_len) % 64, 0);
+        }
+    }
+
+    #[test]
+    fn length_encoding() {
+        let mut padding = [0; 64];
+        Sha1Core::write_padding(3, &mut padding[..61]);
+        assert_eq!(padding[0], 0x80);
+        assert_eq!(padding[53..61], 24u64.to_be_bytes());
+    }
+
+    /// Resuming from the state after two blocks gives the same digest as hashing in one go,
+    /// and the state can be read back from a digest.
+    fn check_from_state<H: MerkleDamgard>() {
+        let data = [0xa5; 300];
+        let processed = 2 * H::BLOCK_SIZE;
+        let mut state = H::INITIAL_STATE;
+        for block in data[..processed].chunks_exact(H::BLOCK_SIZE) {
+            H::compress(&mut state, block.try_into().unwrap());
+        }
+
+        let mut hasher = Hasher::<H>::from_state(state, processed as u64);
+        hasher.update(&data[processed..]);
+        assert_eq!(hasher.finalize(), Hasher::<H>::digest(&data));
+
+        let digest = Hasher::<H>::digest(b"abc");
+        let state = H::state_from_digest(&digest);
+        assert_eq!(H::state_to_digest(&state), digest);
+    }
+
+    #[test]
+    fn from_state() {
+        check_from_state::<Md4Core>();
+        check_from_state::<Sha1Core>();
+        check_from_state::<Sha256Core>();
+    }
+}
@@ -0,0 +1,350 @@
+//! Mersenne Twister (MT19937)
+//!
+//! This is an implementation of the 32-bit and 64-bit variants of the Mersenne Twister PRNG,
+//! following the [reference implementation] by Matsumoto and Nishimura.
+//!
+//! Both implement [`RngCore`], so they can replace `rand::rng()` wherever deterministic output is needed.
+//! The Mersenne Twister is NOT cryptographically secure, its state can be recovered from its outputs.
+//!
+//! [reference implementation]: http://www.math.sci.hiroshima-u.ac.jp/m-mat/MT/emt.html
+
+use rand_core::{RngCore, impls};
+
+use crate::xor;
+
+/// MT19937, the 32-bit Mersenne Twister.
+#[derive(Clone, Debug)]
+pub struct Mt19937 {
+    state: [u32; Self::N],
+    index: usize,
+}
+
+impl Mt19937 {
+    const N: usize = 624;
+    const M: usize = 397;
+    const MATRIX_A: u32 = 0x9908b0df;
+    const UPPER_MASK: u32 = 0x80000000;
+    const LOWER_MASK: u32 = 0x7fffffff;
+
+    /// Seed used by the reference implementation if none is given.
+    pub const DEFAULT_SEED: u32 = 5489;
+
+    /// Creates a new generator seeded with `seed` (`init_genrand`).
+    pub fn new(seed: u32) -> Self {
+        let mut state = [0; Self::N];
+        state[0] = seed;
+        for i in 1..Self::N {
+            let prev = state[i - 1];
+            state[i] = 1812433253u32
+                .wrapping_mul(prev ^ (prev >> 30))
+                .wrapping_add(i as u32);
+        }
+        Self {
+            state,
+            index: Self::N,
+        }
+    }
+
+    /// Creates a generator from its raw internal state.
+    ///
+    /// `index` is the position of the next state word to be output.
+    /// An index of `624` means the state is twisted before the next output.
+    pub fn from_state(state: [u32; Self::N], index: usize) -> Self {
+        assert!(index <= Self::N);
+        Self { state, index }
+    }
+
+    /// Creates a new generator seeded with an array of words (`init_by_array`).
+    pub fn from_key(key: &[u32]) -> Self {
+        assert!(!key.is_empty());
+        let mut rng = Self::new(19650218);
+        let mt = &mut rng.state;
+        let (mut i, mut j) = (1, 0);
+        for _ in 0..Self::N.max(key.len()) {
+            let prev = mt[i - 1] ^ (mt[i - 1] >> 30);
+            mt[i] = (mt[i] ^ prev.wrapping_mul(1664525))
+                .wrapping_add(key[j])
+                .wrapping_add(j as u32);
+            i += 1;
+            j += 1;
+            if i >= Self::N {
+                mt[0] = mt[Self::N - 1];
+                i = 1;
+            }
+            if j >= key.len() {
+                j = 0;
+            }
+        }
+        for _ in 0..Self::N - 1 {
+            let prev = mt[i - 1] ^ (mt[i - 1] >> 30);
+            mt[i] = (mt[i] ^ prev.wrapping_mul(1566083941)).wrapping_sub(i as u32);
+            i += 1;
+            if i >= Self::N {
+                mt[0] = mt[Self::N - 1];
+                i = 1;
+            }
+        }
+        mt[0] = 0x80000000;
+        rng
+    }
+
+    /// Returns the next 32-bit output (`genrand_int32`).
+    pub fn next_output(&mut self) -> u32 {
+        if self.index >= Self::N {
+            self.twist();
+        }
+        let y = self.state[self.index];
+        self.index += 1;
+        Self::temper(y)
+    }
+
+    /// Applies the tempering transform to a single state word.
+    pub fn temper(mut y: u32) -> u32 {
+        y ^= y >> 11;
+        y ^= (y << 7) & 0x9d2c5680;
+        y ^= (y << 15) & 0xefc60000;
+        y ^= y >> 18;
+        y
+    }
+
+    /// Generates the next `N` state words at once.
+    fn twist(&mut self) {
+        for i in 0..Self::N {
+            let y = (self.state[i] & Self::UPPER_MASK)
+                | (self.state[(i + 1) % Self::N] & Self::LOWER_MASK);
+            let mag = if y & 1 == 1 { Self::MATRIX_A } else { 0 };
+            self.state[i] = self.state[(i + Self::M) % Self::N] ^ (y >> 1) ^ mag;
+        }
+        self.index = 0;
+    }
+}
+
+impl Default for Mt19937 {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl RngCore for Mt19937 {
+    fn next_u32(&mut self) -> u32 {
+        self.next_output()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_u32(self)
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dst)
+    }
+}
+
+/// MT19937-64, the 64-bit Mersenne Twister.
+#[derive(Clone, Debug)]
+pub struct Mt19937_64 {
+    state: [u64; Self::N],
+    index: usize,
+}
+
+impl Mt19937_64 {
+    const N: usize = 312;
+    const M: usize = 156;
+    const MATRIX_A: u64 = 0xb5026f5aa96619e9;
+    const UPPER_MASK: u64 = 0xffffffff80000000;
+    const LOWER_MASK: u64 = 0x7fffffff;
+
+    /// Seed used by the reference implementation if none is given.
+    pub const DEFAULT_SEED: u64 = 5489;
+
+    /// Creates a new generator seeded with `seed` (`init_genrand64`).
+    pub fn new(seed: u64) -> Self {
+        let mut state = [0; Self::N];
+        state[0] = seed;
+        for i in 1..Self::N {
+            let prev = state[i - 1];
+            state[i] = 6364136223846793005u64
+                .wrapping_mul(prev ^ (prev >> 62))
+                .wrapping_add(i as u64);
+        }
+        Self {
+            state,
+            index: Self::N,
+        }
+    }
+
+    /// Creates a new generator seeded with an array of words (`init_by_array64`).
+    pub fn from_key(key: &[u64]) -> Self {
+        assert!(!key.is_empty());
+        let mut rng = Self::new(19650218);
+        let mt = &mut rng.state;
+        let (mut i, mut j) = (1, 0);
+        for _ in 0..Self::N.max(key.len()) {
+            let prev = mt[i - 1] ^ (mt[i - 1] >> 62);
+            mt[i] = (mt[i] ^ prev.wrapping_mul(3935559000370003845))
+                .wrapping_add(key[j])
+                .wrapping_add(j as u64);
+            i += 1;
+            j += 1;
+            if i >= Self::N {
+                mt[0] = mt[Self::N - 1];
+                i = 1;
+            }
+            if j >= key.len() {
+                j = 0;
+            }
+        }
+        for _ in 0..Self::N - 1 {
+            let prev = mt[i - 1] ^ (mt[i - 1] >> 62);
+            mt[i] = (mt[i] ^ prev.wrapping_mul(2862933555777941757)).wrapping_sub(i as u64);
+            i += 1;
+            if i >= Self::N {
+                mt[0] = mt[Self::N - 1];
+                i = 1;
+            }
+        }
+        mt[0] = 1 << 63;
+        rng
+    }
+
+    /// Returns the next 64-bit output (`genrand64_int64`).
+    pub fn next_output(&mut self) -> u64 {
+        if self.index >= Self::N {
+            self.twist();
+        }
+        let mut y = self.state[self.index];
+        self.index += 1;
+        y ^= (y >> 29) & 0x5555555555555555;
+        y ^= (y << 17) & 0x71d67fffeda60000;
+        y ^= (y << 37) & 0xfff7eee000000000;
+        y ^= y >> 43;
+        y
+    }
+
+    /// Generates the next `N` state words at once.
+    fn twist(&mut self) {
+        for i in 0..Self::N {
+            let y = (self.state[i] & Self::UPPER_MASK)
+                | (self.state[(i + 1) % Self::N] & Self::LOWER_MASK);
+            let mag = if y & 1 == 1 { Self::MATRIX_A } else { 0 };
+            self.state[i] = self.state[(i + Self::M) % Self::N] ^ (y >> 1) ^ mag;
+        }
+        self.index = 0;
+    }
+}
+
+impl Default for Mt19937_64 {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_SEED)
+    }
+}
+
+impl RngCore for Mt19937_64 {
+    fn next_u32(&mut self) -> u32 {
+        self.next_output() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.next_output()
+    }
+
+    fn fill_bytes(&mut self, dst: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dst)
+    }
+}
+
+/// Stream cipher with the MT19937 outputs as keystream.
+///
+/// The key is a 16-bit seed, so it can trivially be brute-forced.
+#[derive(Clone, Debug)]
+pub struct Mt19937Cipher {
+    seed: u16,
+}
+
+impl Mt19937Cipher {
+    /// Creates a new cipher keyed with `seed`.
+    pub fn new(seed: u16) -> Self {
+        Self { seed }
+    }
+
+    /// Encrypts or decrypts `bytes` in place.
+    ///
+    /// The keystream always starts at the first output of the freshly seeded generator.
+    /// Every 32-bit output provides four keystream bytes (little-endian).
+    pub fn apply_keystream(&self, bytes: &mut [u8]) {
+        let mut rng = Mt19937::new(u32::from(self.seed));
+        let mut keystream = [0; 64];
+        for chunk in bytes.chunks_mut(keystream.len()) {
+            rng.fill_bytes(&mut keystream);
+            xor::encrypt_fixed(chunk, &keystream[..chunk.len()]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Source: http://www.math.sci.hiroshima-u.ac.jp/m-mat/MT/MT2002/CODES/mt19937ar.out
+    #[test]
+    fn reference_output_32() {
+        let mut rng = Mt19937::from_key(&[0x123, 0x234, 0x345, 0x456]);
+        let outputs: [u32; 5] = core::array::from_fn(|_| rng.next_output());
+        assert_eq!(
+            outputs,
+            [1067595299, 955945823, 477289528, 4107218783, 4228976476]
+        );
+    }
+
+    /// Source: http://www.math.sci.hiroshima-u.ac.jp/m-mat/MT/VERSIONS/C-LANG/mt19937-64.out.txt
+    #[test]
+    fn reference_output_64() {
+        let mut rng = Mt19937_64::from_key(&[0x12345, 0x23456, 0x34567, 0x45678]);
+        let outputs: [u64; 3] = core::array::from_fn(|_| rng.next_output());
+        assert_eq!(
+            outputs,
+            [
+                7266447313870364031,
+                4946485549665804864,
+                16945909448695747420
+            ]
+        );
+    }
+
+    /// The 10000th output for the default seed, as required by C++ `std::mt19937(_64)`.
+    #[test]
+    fn default_seed_10000th_output() {
+        let mut rng = Mt19937::default();
+        assert_eq!(rng.next_output(), 3499211612);
+        let output = (1..10000).map(|_| rng.next_output()).last();
+        assert_eq!(output, Some(4123659995));
+
+        let mut rng = Mt19937_64::default();
+        assert_eq!(rng.next_output(), 14514284786278117030);
+        let output = (1..10000).map(|_| rng.next_output()).last();
+        assert_eq!(output, Some(9981545732273789042));
+    }
+
+    #[test]
+    fn cipher_round_trip() {
+        let cipher = Mt19937Cipher::new(0xbeef);
+        let mut buffer =
+            *b"Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby Ice, Ice, baby Ice, Ice";
+        cipher.apply_keystream(&mut buffer);
+        assert_ne!(&buffer[..4], b"Yo, ");
+        cipher.apply_keystream(&mut buffer);
+        assert_eq!(
+            &buffer,
+            b"Yo, VIP Let's kick it Ice, Ice, baby Ice, Ice, baby Ice, Ice, baby Ice, Ice"
+        );
+    }
+
+    #[test]
+    fn rng_core() {
+        let mut a = Mt19937::new(42);
+        let mut b = Mt19937::new(42);
+        let mut bytes = [0; 8];
+        a.fill_bytes(&mut bytes);
+        assert_eq!(bytes[..4], b.next_u32().to_le_bytes());
+        assert_eq!(bytes[4..], b.next_u32().to_le_bytes());
+    }
+}
@@ -0,0 +1,137 @@
+//! SHA-1
+//!
+//! This is an implementation of the SHA-1 hash function, as specified in [FIPS 180-4].
+//!
+//! The incremental interface and state injection are provided by the generic [`Hasher`],
+//! so the hasher can be constructed from an arbitrary internal state, which enables length extension attacks.
+//! SHA-1 is NOT collision resistant anymore and should not be used for new applications.
+//!
+//! [FIPS 180-4]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+
+use hybrid_array::Array;
+use hybrid_array::sizes::{U20, U64};
+
+use crate::merkle_damgard::{Hasher, LengthEncoding, MerkleDamgard};
+
+/// Incremental SHA-1 hasher.
+pub type Sha1 = Hasher<Sha1Core>;
+
+/// SHA-1 compression function and parameters.
+#[derive(Clone, Debug)]
+pub struct Sha1Core;
+
+impl MerkleDamgard for Sha1Core {
+    type BlockSize = U64;
+    type OutputSize = U20;
+    type State = [u32; 5];
+
+    const INITIAL_STATE: [u32; 5] = [0x67452301, 0xefcdab89, 0x98badcfe, 0x10325476, 0xc3d2e1f0];
+    const LENGTH_ENCODING: LengthEncoding = LengthEncoding::BigEndian;
+
+    fn compress(state: &mut [u32; 5], block: &Array<u8, U64>) {
+        compress(state, block);
+    }
+
+    fn state_to_digest(state: &[u32; 5]) -> Array<u8, U20> {
+        let mut digest = Array::<u8, U20>::default();
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    fn state_from_digest(digest: &Array<u8, U20>) -> [u32; 5] {
+        let mut state = [0; 5];
+        for (word, bytes) in state.iter_mut().zip(digest.chunks_exact(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        state
+    }
+}
+
+/// Applies the SHA-1 compression function to `state` with a single 512-bit `block`.
+fn compress(state: &mut [u32; 5], block: &[u8]) {
+    // prepare message schedule
+    let mut w = [0u32; 80];
+    for (word, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for t in 16..80 {
+        w[t] = (w[t - 3] ^ w[t - 8] ^ w[t - 14] ^ w[t - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = *state;
+    for (t, &wt) in w.iter().enumerate() {
+        let (f, k) = match t {
+            0..20 => ((b & c) | (!b & d), 0x5a827999),
+            20..40 => (b ^ c ^ d, 0x6ed9eba1),
+            40..60 => ((b & c) | (b & d) | (c & d), 0x8f1bbcdc),
+            _ => (b ^ c ^ d, 0xca62c1d6),
+        };
+        let temp = a
+            .rotate_left(5)
+            .wrapping_add(f)
+            .wrapping_add(e)
+            .wrapping_add(k)
+            .wrapping_add(wt);
+        e = d;
+        d = c;
+        c = b.rotate_left(30);
+        b = a;
+        a = temp;
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_utils::hex;
+
+    use super::*;
+
+    /// Source: https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+    #[test]
+    fn fips_180_4() {
+        assert_eq!(
+            hex::encode(&Sha1::digest(b"abc")),
+            "a9993e364706816aba3e25717850c26c9cd0d89d"
+        );
+        assert_eq!(
+            hex::encode(&Sha1::digest(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "84983e441c3bd26ebaae4aa1f95129e5e54670f1"
+        );
+        assert_eq!(
+            hex::encode(&Sha1::digest(b"")),
+            "da39a3ee5e6b4b0d3255bfef95601890afd80709"
+        );
+    }
+
+    #[test]
+    fn million_a() {
+        let mut hasher = Sha1::new();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        assert_eq!(
+            hex::encode(&hasher.finalize()),
+            "34aa973cd4c4daa4f61eeb2bdbad27316534016f"
+        );
+    }
+
+    #[test]
+    fn incremental() {
+        let data = [0x5a; 300];
+        let expected = Sha1::digest(&data);
+        for split in [0, 1, 55, 56, 63, 64, 65, 128, 299, 300] {
+            let mut hasher = Sha1::new();
+            hasher.update(&data[..split]);
+            hasher.update(&data[split..]);
+            assert_eq!(hasher.finalize(), expected);
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! SHA-256 and SHA-224
+//!
+//! This is an implementation of the SHA-256 and SHA-224 hash functions, as specified in [FIPS 180-4].
+//!
+//! Both share the same compression function and only differ in initial state and output length.
+//! SHA-224 truncates the final state, which makes it resistant to naive length extension.
+//!
+//! [FIPS 180-4]: https://nvlpubs.nist.gov/nistpubs/FIPS/NIST.FIPS.180-4.pdf
+
+use hybrid_array::Array;
+use hybrid_array::sizes::{U28, U32, U64};
+
+use crate::merkle_damgard::{Hasher, LengthEncoding, MerkleDamgard};
+
+/// Incremental SHA-256 hasher.
+pub type Sha256 = Hasher<Sha256Core>;
+
+/// Incremental SHA-224 hasher.
+pub type Sha224 = Hasher<Sha224Core>;
+
+/// SHA-256 compression function and parameters.
+#[derive(Clone, Debug)]
+pub struct Sha256Core;
+
+/// SHA-224 compression function and parameters.
+#[derive(Clone, Debug)]
+pub struct Sha224Core;
+
+impl MerkleDamgard for Sha256Core {
+    type BlockSize = U64;
+    type OutputSize = U32;
+    type State = [u32; 8];
+
+    const INITIAL_STATE: [u32; 8] = [
+        0x6a09e667, 0xbb67ae85, 0x3c6ef372, 0xa54ff53a, 0x510e527f, 0x9b05688c, 0x1f83d9ab,
+        0x5be0cd19,
+    ];
+    const LENGTH_ENCODING: LengthEncoding = LengthEncoding::BigEndian;
+
+    fn compress(state: &mut [u32; 8], block: &Array<u8, U64>) {
+        compress(state, block);
+    }
+
+    fn state_to_digest(state: &[u32; 8]) -> Array<u8, U32> {
+        let mut digest = Array::<u8, U32>::default();
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    fn state_from_digest(digest: &Array<u8, U32>) -> [u32; 8] {
+        let mut state = [0; 8];
+        for (word, bytes) in state.iter_mut().zip(digest.chunks_exact(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        state
+    }
+}
+
+impl MerkleDamgard for Sha224Core {
+    type BlockSize = U64;
+    type OutputSize = U28;
+    type State = [u32; 8];
+
+    const INITIAL_STATE: [u32; 8] = [
+        0xc1059ed8, 0x367cd507, 0x3070dd17, 0xf70e5939, 0xffc00b31, 0x68581511, 0x64f98fa7,
+        0xbefa4fa4,
+    ];
+    const LENGTH_ENCODING: LengthEncoding = LengthEncoding::BigEndian;
+
+    fn compress(state: &mut [u32; 8], block: &Array<u8, U64>) {
+        compress(state, block);
+    }
+
+    fn state_to_digest(state: &[u32; 8]) -> Array<u8, U28> {
+        let mut digest = Array::<u8, U28>::default();
+        for (bytes, word) in digest.chunks_exact_mut(4).zip(state) {
+            bytes.copy_from_slice(&word.to_be_bytes());
+        }
+        digest
+    }
+
+    /// Recovers the first seven state words, the last one is set to zero.
+    ///
+    /// The last word is not part of the digest, so it has to be guessed separately.
+    fn state_from_digest(digest: &Array<u8, U28>) -> [u32; 8] {
+        let mut state = [0; 8];
+        for (word, bytes) in state.iter_mut().zip(digest.chunks_exact(4)) {
+            *word = u32::from_be_bytes(bytes.try_into().unwrap());
+        }
+        state
+    }
+}
+
+const K: [u32; 64] = [
+    0x428a2f98, 0x71374491, 0xb5c0fbcf, 0xe9b5dba5, 0x3956c25b, 0x59f111f1, 0x923f82a4, 0xab1c5ed5,
+    0xd807aa98, 0x12835b01, 0x243185be, 0x550c7dc3, 0x72be5d74, 0x80deb1fe, 0x9bdc06a7, 0xc19bf174,
+    0xe49b69c1, 0xefbe4786, 0x0fc19dc6, 0x240ca1cc, 0x2de92c6f, 0x4a7484aa, 0x5cb0a9dc, 0x76f988da,
+    0x983e5152, 0xa831c66d, 0xb00327c8, 0xbf597fc7, 0xc6e00bf3, 0xd5a79147, 0x06ca6351, 0x14292967,
+    0x27b70a85, 0x2e1b2138, 0x4d2c6dfc, 0x53380d13, 0x650a7354, 0x766a0abb, 0x81c2c92e, 0x92722c85,
+    0xa2bfe8a1, 0xa81a664b, 0xc24b8b70, 0xc76c51a3, 0xd192e819, 0xd6990624, 0xf40e3585, 0x106aa070,
+    0x19a4c116, 0x1e376c08, 0x2748774c, 0x34b0bcb5, 0x391c0cb3, 0x4ed8aa4a, 0x5b9cca4f, 0x682e6ff3,
+    0x748f82ee, 0x78a5636f, 0x84c87814, 0x8cc70208, 0x90befffa, 0xa4506ceb, 0xbef9a3f7, 0xc67178f2,
+];
+
+/// Applies the SHA-256 compression function to `state` with a single 512-bit `block`.
+fn compress(state: &mut [u32; 8], block: &[u8]) {
+    // prepare message schedule
+    let mut w = [0u32; 64];
+    for (word, bytes) in w.iter_mut().zip(block.chunks_exact(4)) {
+        *word = u32::from_be_bytes(bytes.try_into().unwrap());
+    }
+    for t in 16..64 {
+        let s0 = w[t - 15].rotate_right(7) ^ w[t - 15].rotate_right(18) ^ (w[t - 15] >> 3);
+        let s1 = w[t - 2].rotate_right(17) ^ w[t - 2].rotate_right(19) ^ (w[t - 2] >> 10);
+        w[t] = w[t - 16]
+            .wrapping_add(s0)
+            .wrapping_add(w[t - 7])
+            .wrapping_add(s1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e, mut f, mut g, mut h] = *state;
+    for (&kt, &wt) in K.iter().zip(&w) {
+        let s1 = e.rotate_right(6) ^ e.rotate_right(11) ^ e.rotate_right(25);
+        let ch = (e & f) ^ (!e & g);
+        let temp1 = h
+            .wrapping_add(s1)
+            .wrapping_add(ch)
+            .wrapping_add(kt)
+            .wrapping_add(wt);
+        let s0 = a.rotate_right(2) ^ a.rotate_right(13) ^ a.rotate_right(22);
+        let maj = (a & b) ^ (a & c) ^ (b & c);
+        let temp2 = s0.wrapping_add(maj);
+        h = g;
+        g = f;
+        f = e;
+        e = d.wrapping_add(temp1);
+        d = c;
+        c = b;
+        b = a;
+        a = temp1.wrapping_add(temp2);
+    }
+
+    for (s, v) in state.iter_mut().zip([a, b, c, d, e, f, g, h]) {
+        *s = s.wrapping_add(v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_utils::hex;
+
+    use super::*;
+
+    /// Source: https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+    #[test]
+    fn fips_180_4_sha256() {
+        assert_eq!(
+            hex::encode(&Sha256::digest(b"abc")),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            hex::encode(&Sha256::digest(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "248d6a61d20638b8e5c026930c3e6039a33ce45964ff2167f6ecedd419db06c1"
+        );
+        assert_eq!(
+            hex::encode(&Sha256::digest(b"")),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    /// Source: https://csrc.nist.gov/projects/cryptographic-standards-and-guidelines/example-values
+    #[test]
+    fn fips_180_4_sha224() {
+        assert_eq!(
+            hex::encode(&Sha224::digest(b"abc")),
+            "23097d223405d8228642a477bda255b32aadbce4bda0b3f7e36c9da7"
+        );
+        assert_eq!(
+            hex::encode(&Sha224::digest(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "75388b16512776cc5dba5da1fd890150b0c6455cb4f58b1952522525"
+        );
+    }
+
+    #[test]
+    fn million_a() {
+        let mut hasher = Sha256::new();
+        for _ in 0..1000 {
+            hasher.update(&[b'a'; 1000]);
+        }
+        assert_eq!(
+            hex::encode(&hasher.finalize()),
+            "cdc76e5c9914fb9281a1c7e284d73e67f1809a48a497200e046d39ccc7112cd0"
+        );
+    }
+}
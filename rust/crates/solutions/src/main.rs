@@ -4,6 +4,9 @@
 
 mod set1;
 mod set2;
+mod set3;
+mod set4;
+mod set5;
 
 fn main() {
     println!(
@@ -24,15 +24,23 @@ pub fn solve_challenge(input_hex: &str) -> String {
 ///
 /// Returns the best guess of the key and decrypted string.
 pub fn crack_single_byte_xor(ciphertext: &[u8]) -> (u8, String) {
+    crack_single_byte_xor_with_keys(ciphertext, (0u8..=255).filter(u8::is_ascii))
+}
+
+/// Attempts to decrypt a single-byte XOR ciphertext, trying only the given `keys`.
+///
+/// Unlike [`crack_single_byte_xor`], this makes no assumption about the key,
+/// e.g. it can recover arbitrary keystream bytes when passed all 256 keys.
+///
+/// Returns the best guess of the key and decrypted string.
+pub fn crack_single_byte_xor_with_keys(
+    ciphertext: &[u8],
+    keys: impl IntoIterator<Item = u8>,
+) -> (u8, String) {
     let mut best_key = 0;
     let mut best_guess = String::new();
     let mut best_score = 0.0;
-    for key in 0u8..=255 {
-        // ignore non ASCII
-        if !key.is_ascii() {
-            continue;
-        }
-
+    for key in keys {
         // apply XOR
         let mut buffer = ciphertext.to_vec();
         xor::decrypt_repeating(&mut buffer, &[key]);
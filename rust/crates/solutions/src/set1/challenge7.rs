@@ -2,6 +2,7 @@
 //!
 //! Solution to [Challenge 7](https://cryptopals.com/sets/1/challenges/7) of Cryptopals.
 
+use cryptopals_modes::ecb::Ecb;
 use cryptopals_primitives::{BlockCipher, aes::Aes128};
 use cryptopals_utils::base64;
 
@@ -13,16 +14,13 @@ pub fn decrypt_aes(ciphertext_base64: &str, key: &[u8]) -> String {
     let ciphertext_base64 = ciphertext_base64
         .lines()
         .fold(String::new(), |acc, line| acc + line.trim());
-    let ciphertext = base64::decode(&ciphertext_base64);
+    let mut buffer = base64::decode(&ciphertext_base64);
 
     // decrypt with key
-    let mut aes = Aes128::new(key.try_into().unwrap());
-    let mut plaintext = String::new();
-    for chunk in ciphertext.chunks(16) {
-        let output = aes.decrypt_block(chunk.try_into().unwrap());
-        plaintext += &String::from_utf8_lossy(&output);
-    }
-    plaintext
+    let aes = Aes128::new(key.try_into().unwrap());
+    let mut ecb = Ecb::new(aes);
+    ecb.decrypt(&mut buffer).unwrap();
+    String::from_utf8_lossy(&buffer).to_string()
 }
 
 #[cfg(test)]
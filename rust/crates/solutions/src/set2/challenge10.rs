@@ -19,7 +19,9 @@ pub fn decrypt_cbc(ciphertext_base64: &str, key: &[u8]) -> Vec<u8> {
     let aes = Aes128::new(key.try_into().unwrap());
     let mut cbc = Cbc::new(aes, [0u8; 16].into());
 
-    cbc.decrypt_padded::<Pkcs7<U16>>(&mut ciphertext).to_vec()
+    cbc.decrypt_padded::<Pkcs7<U16>>(&mut ciphertext)
+        .unwrap()
+        .to_vec()
 }
 
 #[cfg(test)]
@@ -34,7 +34,9 @@ mod tests {
         cbc.encrypt_padded::<Pkcs7<U16>>(buffer.as_mut_slice(), 30);
         xor::decrypt_fixed(&mut buffer[0..2], b"BB");
         xor::encrypt_fixed(&mut buffer[0..2], b"CC");
-        let plaintext = cbc.decrypt_padded::<Pkcs7<U16>>(buffer.as_mut_slice());
+        let plaintext = cbc
+            .decrypt_padded::<Pkcs7<U16>>(buffer.as_mut_slice())
+            .unwrap();
         assert!(&plaintext[16..18] == b"CC");
     }
 
@@ -0,0 +1,7 @@
+//! # Set 3
+//!
+//! Solutions for [Set 3](https://cryptopals.com/sets/3) of Cryptopals.
+
+pub mod challenge17;
+pub mod challenge19;
+pub mod challenge20;
@@ -0,0 +1,132 @@
+//! # Challenge 17
+//!
+//! Solution to [Challenge 17](https://cryptopals.com/sets/3/challenges/17) of Cryptopals.
+
+use cryptopals_padding::pkcs7::Pkcs7;
+use hybrid_array::sizes::U16;
+
+const BLOCK_LENGTH_BYTES: usize = 16;
+
+/// Launches the CBC padding oracle attack.
+///
+/// Works against any `oracle` that takes `(iv, ciphertext)` and
+/// reports whether the corresponding plaintext is correctly padded.
+///
+/// Returns the recovered plaintext (without padding) and the number of oracle queries used.
+pub fn padding_oracle_attack(
+    iv: &[u8],
+    ciphertext: &[u8],
+    oracle: impl Fn(&[u8], &[u8]) -> bool,
+) -> (Vec<u8>, usize) {
+    let mut queries = 0;
+    let mut plaintext = Vec::with_capacity(ciphertext.len());
+
+    let mut previous_block = iv;
+    for block in ciphertext.chunks_exact(BLOCK_LENGTH_BYTES) {
+        let (intermediate, block_queries) = recover_intermediate_block(block, &oracle);
+        queries += block_queries;
+        plaintext.extend(intermediate.iter().zip(previous_block).map(|(i, p)| i ^ p));
+        previous_block = block;
+    }
+
+    let unpadded_len = Pkcs7::<U16>::unpad_checked(&plaintext).unwrap().len();
+    plaintext.truncate(unpadded_len);
+    (plaintext, queries)
+}
+
+/// Recovers the block cipher decryption of `block`, i.e. the plaintext before XORing the IV.
+///
+/// Returns the intermediate block and the number of oracle queries used.
+fn recover_intermediate_block(
+    block: &[u8],
+    oracle: &impl Fn(&[u8], &[u8]) -> bool,
+) -> ([u8; BLOCK_LENGTH_BYTES], usize) {
+    let mut queries = 0;
+    let mut intermediate = [0; BLOCK_LENGTH_BYTES];
+    let mut forged_iv = [0; BLOCK_LENGTH_BYTES];
+
+    for padding_len in 1..=BLOCK_LENGTH_BYTES {
+        let pos = BLOCK_LENGTH_BYTES - padding_len;
+
+        // make already recovered bytes decrypt to the padding byte
+        for i in pos + 1..BLOCK_LENGTH_BYTES {
+            forged_iv[i] = intermediate[i] ^ padding_len as u8;
+        }
+
+        let mut found = false;
+        for guess in 0..=255 {
+            forged_iv[pos] = guess;
+            queries += 1;
+            if !oracle(&forged_iv, block) {
+                continue;
+            }
+
+            // for the last byte, `\x02\x02` (etc.) might also be valid padding
+            // rule this out by changing the second to last byte
+            if padding_len == 1 {
+                forged_iv[pos - 1] ^= 0xff;
+                queries += 1;
+                let still_valid = oracle(&forged_iv, block);
+                forged_iv[pos - 1] ^= 0xff;
+                if !still_valid {
+                    continue;
+                }
+            }
+
+            intermediate[pos] = guess ^ padding_len as u8;
+            found = true;
+            break;
+        }
+        assert!(found, "oracle never reported valid padding");
+    }
+
+    (intermediate, queries)
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_attacks::CbcPaddingOracle;
+
+    use super::*;
+
+    const PLAINTEXTS: [&str; 10] = [
+        "000000Now that the party is jumping",
+        "000001With the bass kicked in and the Vega's are pumpin'",
+        "000002Quick to the point, to the point, no faking",
+        "000003Cooking MC's like a pound of bacon",
+        "000004Burning 'em, if you ain't quick and nimble",
+        "000005I go crazy when I hear a cymbal",
+        "000006And a high hat with a souped up tempo",
+        "000007I'm on a roll, it's time to go solo",
+        "000008ollin' in my five point oh",
+        "000009ith my rag-top down so my hair can blow",
+    ];
+
+    #[test]
+    fn challenge() {
+        let oracle = CbcPaddingOracle::new();
+        for _ in 0..10 {
+            let (iv, ciphertext) = oracle.encrypt();
+            let (plaintext, queries) = padding_oracle_attack(&iv, &ciphertext, |iv, ciphertext| {
+                oracle.check_padding(iv, ciphertext)
+            });
+            let plaintext = String::from_utf8(plaintext).unwrap();
+            println!("plaintext: {}", &plaintext);
+            println!("recovered in {} queries", queries);
+            assert!(PLAINTEXTS.contains(&plaintext.as_str()));
+            assert!(queries < 257 * ciphertext.len());
+        }
+    }
+
+    #[test]
+    fn constant_time_unpadding() {
+        // closing the timing channel does not help, validity itself is the leak
+        let oracle = CbcPaddingOracle::new();
+        let (iv, ciphertext) = oracle.encrypt();
+        let (plaintext, _) = padding_oracle_attack(&iv, &ciphertext, |iv, ciphertext| {
+            oracle.check_padding_constant_time(iv, ciphertext)
+        });
+        let plaintext = String::from_utf8(plaintext).unwrap();
+        assert!(PLAINTEXTS.contains(&plaintext.as_str()));
+    }
+}
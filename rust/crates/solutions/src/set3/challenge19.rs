@@ -0,0 +1,116 @@
+//! # Challenge 19
+//!
+//! Solution to [Challenge 19](https://cryptopals.com/sets/3/challenges/19) of Cryptopals.
+//!
+//! Instead of guessing the keystream by hand, this uses the statistical approach
+//! column by column, which also copes with ciphertexts of different lengths.
+
+use cryptopals_modes::ctr::Ctr;
+use cryptopals_primitives::{BlockCipher, aes::Aes128, xor};
+use cryptopals_utils::base64;
+use rand::prelude::*;
+
+use crate::set1::challenge3;
+
+/// Decrypts the base64-encoded lines that were encrypted under the same CTR keystream.
+///
+/// Returns the best guess for each plaintext.
+pub fn solve_challenge(plaintexts_base64: &str) -> Vec<String> {
+    let plaintexts = plaintexts_base64
+        .lines()
+        .map(|line| base64::decode(line.trim()))
+        .collect::<Vec<_>>();
+    let ciphertexts = encrypt_with_fixed_nonce(&plaintexts);
+    let keystream = recover_keystream_columnwise(&ciphertexts);
+    decrypt_with_keystream(&ciphertexts, &keystream)
+}
+
+/// Encrypts every plaintext with AES-CTR under the same random key and a nonce of zero.
+pub fn encrypt_with_fixed_nonce(plaintexts: &[Vec<u8>]) -> Vec<Vec<u8>> {
+    let mut key = [0; 16];
+    rand::rng().fill_bytes(&mut key);
+    let mut ctr = Ctr::with_nonce(Aes128::new(key.into()), 0);
+
+    let mut ciphertexts = plaintexts.to_vec();
+    for ciphertext in &mut ciphertexts {
+        ctr.apply_keystream(ciphertext);
+    }
+    ciphertexts
+}
+
+/// Recovers the keystream shared by `ciphertexts`, one column at a time.
+///
+/// Column `i` consists of byte `i` of every ciphertext that is long enough,
+/// so the recovered keystream is as long as the longest ciphertext.
+/// Columns near the end contain only few bytes, so they are less reliable.
+pub fn recover_keystream_columnwise<C: AsRef<[u8]>>(ciphertexts: &[C]) -> Vec<u8> {
+    let max_len = ciphertexts
+        .iter()
+        .map(|c| c.as_ref().len())
+        .max()
+        .unwrap_or(0);
+
+    let mut keystream = Vec::with_capacity(max_len);
+    for i in 0..max_len {
+        let column = ciphertexts
+            .iter()
+            .filter_map(|c| c.as_ref().get(i))
+            .copied()
+            .collect::<Vec<_>>();
+        let (key_byte, _) = challenge3::crack_single_byte_xor_with_keys(&column, 0..=255);
+        keystream.push(key_byte);
+    }
+    keystream
+}
+
+/// Decrypts every ciphertext with (a prefix of) `keystream`.
+///
+/// Ciphertexts longer than `keystream` are truncated.
+pub fn decrypt_with_keystream<C: AsRef<[u8]>>(ciphertexts: &[C], keystream: &[u8]) -> Vec<String> {
+    ciphertexts
+        .iter()
+        .map(|c| {
+            let len = c.as_ref().len().min(keystream.len());
+            let mut buffer = c.as_ref()[..len].to_vec();
+            xor::decrypt_fixed(&mut buffer, &keystream[..len]);
+            String::from_utf8_lossy(&buffer).to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATA: &str = include_str!("../../../../../data/set3/19.txt");
+
+    #[test]
+    fn challenge() {
+        let expected = DATA
+            .lines()
+            .map(|line| String::from_utf8(base64::decode(line)).unwrap())
+            .collect::<Vec<_>>();
+        let plaintexts = solve_challenge(DATA);
+        assert_eq!(plaintexts.len(), expected.len());
+
+        // columns shared by all lines are recovered exactly, except for the capitalized first one
+        let min_len = expected.iter().map(String::len).min().unwrap();
+        for (plaintext, expected) in plaintexts.iter().zip(&expected) {
+            assert_eq!(
+                plaintext.as_bytes().get(1..min_len),
+                expected.as_bytes().get(1..min_len)
+            );
+        }
+
+        // the rare bytes at the end might be off, but the bulk must be correct
+        let total = expected.iter().map(String::len).sum::<usize>();
+        let correct = plaintexts
+            .iter()
+            .zip(&expected)
+            .flat_map(|(p, e)| p.bytes().zip(e.bytes()))
+            .filter(|(p, e)| p == e)
+            .count();
+        println!("correct: {correct}/{total}");
+        assert!(correct * 100 > total * 85);
+    }
+}
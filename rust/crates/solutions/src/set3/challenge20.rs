@@ -0,0 +1,91 @@
+//! # Challenge 20
+//!
+//! Solution to [Challenge 20](https://cryptopals.com/sets/3/challenges/20) of Cryptopals.
+
+use cryptopals_utils::base64;
+
+use crate::set1::challenge3;
+use crate::set3::challenge19;
+
+/// Decrypts the base64-encoded lines that were encrypted under the same CTR keystream.
+///
+/// Returns the best guess for each plaintext, truncated to the length of the shortest one.
+pub fn solve_challenge(plaintexts_base64: &str) -> Vec<String> {
+    let plaintexts = plaintexts_base64
+        .lines()
+        .map(|line| base64::decode(line.trim()))
+        .collect::<Vec<_>>();
+    let ciphertexts = challenge19::encrypt_with_fixed_nonce(&plaintexts);
+    let keystream = recover_keystream_truncated(&ciphertexts);
+    challenge19::decrypt_with_keystream(&ciphertexts, &keystream)
+}
+
+/// Recovers the keystream shared by `ciphertexts`, truncating them to the shortest one.
+///
+/// After truncation the ciphertexts are the same as a repeating-key XOR ciphertext
+/// with known key length, so each key byte is cracked as in Challenge 6.
+pub fn recover_keystream_truncated<C: AsRef<[u8]>>(ciphertexts: &[C]) -> Vec<u8> {
+    let key_length = ciphertexts
+        .iter()
+        .map(|c| c.as_ref().len())
+        .min()
+        .unwrap_or(0);
+    let concatenated = ciphertexts
+        .iter()
+        .flat_map(|c| &c.as_ref()[..key_length])
+        .copied()
+        .collect::<Vec<_>>();
+
+    let mut keystream = Vec::with_capacity(key_length);
+    for i in 0..key_length {
+        let offset_ciphertext = concatenated
+            .iter()
+            .skip(i)
+            .step_by(key_length)
+            .copied()
+            .collect::<Vec<_>>();
+        let (key_byte, _) =
+            challenge3::crack_single_byte_xor_with_keys(&offset_ciphertext, 0..=255);
+        keystream.push(key_byte);
+    }
+    keystream
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+
+    use super::*;
+
+    /// The challenge data is not checked in yet, so the test only runs once it is downloaded
+    /// from <https://cryptopals.com/static/challenge-data/20.txt> into `data/set3/20.txt`.
+    #[test]
+    #[ignore = "needs data/set3/20.txt from cryptopals.com"]
+    fn truncated() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/../../../data/set3/20.txt");
+        let data = fs::read_to_string(path).expect("challenge data is missing");
+        let expected = data
+            .lines()
+            .map(|line| String::from_utf8(base64::decode(line)).unwrap())
+            .collect::<Vec<_>>();
+        let min_len = expected.iter().map(String::len).min().unwrap();
+
+        let plaintexts = solve_challenge(&data);
+        assert_eq!(plaintexts.len(), expected.len());
+        for plaintext in &plaintexts {
+            assert_eq!(plaintext.len(), min_len);
+        }
+
+        // the first column is mostly capitals, which frequency analysis gets wrong,
+        // and a few rare bytes in the other columns might be off as well
+        let total = expected.len() * (min_len - 1);
+        let correct = plaintexts
+            .iter()
+            .zip(&expected)
+            .flat_map(|(p, e)| p.bytes().zip(e.bytes()).take(min_len).skip(1))
+            .filter(|(p, e)| p == e)
+            .count();
+        println!("correct: {correct}/{total}");
+        assert!(correct * 100 > total * 85);
+    }
+}
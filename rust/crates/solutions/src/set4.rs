@@ -0,0 +1,11 @@
+//! # Set 4
+//!
+//! Solutions for [Set 4](https://cryptopals.com/sets/4) of Cryptopals.
+
+pub mod challenge25;
+pub mod challenge26;
+pub mod challenge27;
+pub mod challenge29;
+pub mod challenge30;
+pub mod challenge31;
+pub mod challenge32;
@@ -0,0 +1,34 @@
+//! # Challenge 25
+//!
+//! Solution to [Challenge 25](https://cryptopals.com/sets/4/challenges/25) of Cryptopals.
+
+use cryptopals_attacks::CtrEditOracle;
+use cryptopals_primitives::xor;
+
+/// Recovers the plaintext behind `ciphertext` using the oracle's edit function.
+///
+/// Overwriting everything with zeros makes the oracle hand back the raw keystream.
+pub fn recover_plaintext(oracle: &CtrEditOracle, ciphertext: &[u8]) -> Vec<u8> {
+    let zeros = vec![0; ciphertext.len()];
+    let keystream = oracle.edit(ciphertext, 0, &zeros);
+    let mut plaintext = ciphertext.to_vec();
+    xor::encrypt_fixed(&mut plaintext, &keystream);
+    plaintext
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::set1::challenge7;
+
+    const DATA: &str = include_str!("../../../../../data/set1/7.txt");
+
+    #[test]
+    fn challenge() {
+        let plaintext = challenge7::decrypt_aes(DATA, b"YELLOW SUBMARINE");
+        let oracle = CtrEditOracle::new();
+        let ciphertext = oracle.encrypt(plaintext.as_bytes());
+        let recovered = recover_plaintext(&oracle, &ciphertext);
+        assert_eq!(recovered, plaintext.as_bytes());
+    }
+}
@@ -0,0 +1,39 @@
+//! # Challenge 26
+//!
+//! Solution to [Challenge 26](https://cryptopals.com/sets/4/challenges/26) of Cryptopals.
+
+use cryptopals_attacks::CtrUserdataOracle;
+use cryptopals_primitives::xor;
+use cryptopals_utils::hex;
+
+const TARGET: &[u8] = b";admin=true;";
+
+/// Forges a ciphertext that decrypts to a string containing `;admin=true;`.
+///
+/// The offset of the user data is found by encrypting two inputs that differ only in their first byte.
+/// Since CTR is a stream cipher, flipping a ciphertext bit flips exactly the same plaintext bit.
+pub fn ctr_bitflipping(oracle: &CtrUserdataOracle) -> String {
+    let first = hex::decode(&oracle.encrypt("A"));
+    let second = hex::decode(&oracle.encrypt("B"));
+    let offset = first.iter().zip(&second).position(|(a, b)| a != b).unwrap();
+
+    let input = "A".repeat(TARGET.len());
+    let mut ciphertext = hex::decode(&oracle.encrypt(&input));
+    let injected = &mut ciphertext[offset..offset + TARGET.len()];
+    xor::decrypt_fixed(injected, input.as_bytes());
+    xor::encrypt_fixed(injected, TARGET);
+    hex::encode(&ciphertext)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn challenge() {
+        // run attack against oracle
+        let oracle = CtrUserdataOracle::new();
+        let successful_ciphertext = ctr_bitflipping(&oracle);
+        assert!(oracle.try_admin_action(&successful_ciphertext));
+    }
+}
@@ -0,0 +1,53 @@
+//! # Challenge 27
+//!
+//! Solution to [Challenge 27](https://cryptopals.com/sets/4/challenges/27) of Cryptopals.
+
+use cryptopals_attacks::{CbcKeyAsIvOracle, KeyAsIvError};
+
+const BLOCK_LENGTH_BYTES: usize = 16;
+
+/// Recovers the key of an oracle that also uses it as CBC IV.
+///
+/// Rewrites the ciphertext to `C1 || 0 || C1 || ...`, keeping the remaining blocks so the padding stays valid.
+/// Then the first plaintext block is `D(C1) ^ key` and the third one is `D(C1)`.
+///
+/// Returns `None` if the oracle accepts the forged ciphertext, which happens with negligible probability.
+pub fn recover_key(oracle: &CbcKeyAsIvOracle) -> Option<[u8; 16]> {
+    let mut ciphertext = oracle.encrypt("");
+    assert!(ciphertext.len() >= 4 * BLOCK_LENGTH_BYTES);
+
+    let (first, rest) = ciphertext.split_at_mut(BLOCK_LENGTH_BYTES);
+    rest[..BLOCK_LENGTH_BYTES].fill(0);
+    rest[BLOCK_LENGTH_BYTES..2 * BLOCK_LENGTH_BYTES].copy_from_slice(first);
+
+    let Err(KeyAsIvError::NonAscii { plaintext }) = oracle.decrypt(&ciphertext) else {
+        return None;
+    };
+    let mut key = [0; 16];
+    for (i, k) in key.iter_mut().enumerate() {
+        *k = plaintext[i] ^ plaintext[2 * BLOCK_LENGTH_BYTES + i];
+    }
+    Some(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_modes::cbc::Cbc;
+    use cryptopals_padding::pkcs7::Pkcs7;
+    use cryptopals_primitives::{BlockCipher, aes::Aes128};
+    use hybrid_array::sizes::U16;
+
+    use super::*;
+
+    #[test]
+    fn challenge() {
+        let oracle = CbcKeyAsIvOracle::new();
+        let key = recover_key(&oracle).unwrap();
+
+        // the recovered key decrypts arbitrary ciphertexts on its own
+        let mut ciphertext = oracle.encrypt("secret");
+        let mut cbc = Cbc::new(Aes128::new(key.into()), key.into());
+        let plaintext = cbc.decrypt_padded::<Pkcs7<U16>>(&mut ciphertext).unwrap();
+        assert!(plaintext.windows(6).any(|w| w == b"secret"));
+    }
+}
@@ -0,0 +1,85 @@
+//! # Challenge 29
+//!
+//! Solution to [Challenge 29](https://cryptopals.com/sets/4/challenges/29) of Cryptopals.
+//!
+//! The attack only relies on [`MerkleDamgard`], so it works against any such hash,
+//! regardless of the endianness of the length padding.
+
+use cryptopals_attacks::SecretPrefixMac;
+use cryptopals_primitives::merkle_damgard::{Hasher, MerkleDamgard};
+use hybrid_array::Array;
+
+/// Longest secret key the attack tries.
+const MAX_KEY_LENGTH: usize = 128;
+
+/// Result of a successful length extension attack.
+pub struct ForgedMessage<H: MerkleDamgard> {
+    /// Original message, glue padding and extension.
+    pub message: Vec<u8>,
+    /// Valid MAC for `message`.
+    pub mac: Array<u8, H::OutputSize>,
+    /// Length of the oracle's secret key.
+    pub key_len: usize,
+}
+
+/// Extends `message`, for which `mac` is known, with `extension`.
+///
+/// Resumes hashing from the state contained in `mac` and uses the oracle to find the key length.
+/// Returns `None` if no key length up to `MAX_KEY_LENGTH` gives a valid MAC.
+pub fn length_extension_attack<H: MerkleDamgard>(
+    oracle: &SecretPrefixMac<H>,
+    message: &[u8],
+    mac: &Array<u8, H::OutputSize>,
+    extension: &[u8],
+) -> Option<ForgedMessage<H>> {
+    let state = H::state_from_digest(mac);
+    (0..=MAX_KEY_LENGTH).find_map(|key_len| {
+        let (forged_message, forged_mac) = extend::<H>(state, key_len, message, extension);
+        oracle
+            .verify(&forged_message, &forged_mac)
+            .then_some(ForgedMessage {
+                message: forged_message,
+                mac: forged_mac,
+                key_len,
+            })
+    })
+}
+
+/// Computes the forged message and MAC, assuming a secret key of `key_len` bytes.
+fn extend<H: MerkleDamgard>(
+    state: H::State,
+    key_len: usize,
+    message: &[u8],
+    extension: &[u8],
+) -> (Vec<u8>, Array<u8, H::OutputSize>) {
+    let original_len = (key_len + message.len()) as u64;
+    let glue_len = H::padding_len(original_len);
+
+    let mut forged_message = message.to_vec();
+    forged_message.resize(message.len() + glue_len, 0);
+    H::write_padding(original_len, &mut forged_message[message.len()..]);
+    forged_message.extend_from_slice(extension);
+
+    let mut hasher = Hasher::<H>::from_state(state, original_len + glue_len as u64);
+    hasher.update(extension);
+    (forged_message, hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_primitives::sha1::Sha1Core;
+
+    use super::*;
+
+    #[test]
+    fn challenge() {
+        let oracle = SecretPrefixMac::<Sha1Core>::new();
+        let message = SecretPrefixMac::<Sha1Core>::MESSAGE;
+        let mac = oracle.mac(message);
+
+        let forged = length_extension_attack(&oracle, message, &mac, b";admin=true").unwrap();
+        assert!(forged.message.starts_with(message));
+        assert!(oracle.try_admin_action(&forged.message, &forged.mac));
+        assert!((1..=64).contains(&forged.key_len));
+    }
+}
@@ -0,0 +1,26 @@
+//! # Challenge 30
+//!
+//! Solution to [Challenge 30](https://cryptopals.com/sets/4/challenges/30) of Cryptopals.
+//!
+//! Reuses the generic attack from [Challenge 29](super::challenge29),
+//! MD4 only differs from SHA-1 in its little-endian length padding.
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_attacks::SecretPrefixMac;
+    use cryptopals_primitives::md4::Md4Core;
+
+    use crate::set4::challenge29::length_extension_attack;
+
+    #[test]
+    fn challenge() {
+        let oracle = SecretPrefixMac::<Md4Core>::new();
+        let message = SecretPrefixMac::<Md4Core>::MESSAGE;
+        let mac = oracle.mac(message);
+
+        let forged = length_extension_attack(&oracle, message, &mac, b";admin=true").unwrap();
+        assert!(forged.message.starts_with(message));
+        assert!(oracle.try_admin_action(&forged.message, &forged.mac));
+        assert!((1..=64).contains(&forged.key_len));
+    }
+}
@@ -0,0 +1,232 @@
+//! # Challenge 31
+//!
+//! Solution to [Challenge 31](https://cryptopals.com/sets/4/challenges/31) of Cryptopals.
+//!
+//! Every request is timed, and the candidate byte with the slowest response is kept.
+//! Samples for all candidates are taken in interleaved rounds, so that slow drifts
+//! in system load affect every candidate alike.
+//! Each round visits the candidates in a new random order, so that anything tied to the
+//! position within a round does not always favor the same candidates.
+
+use std::io;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use cryptopals_attacks::hmac_timing;
+use rand::prelude::*;
+
+/// Length of an HMAC-SHA1 tag in bytes.
+const SIGNATURE_LENGTH: usize = 20;
+
+/// Upper bound for the number of sampling rounds, as a multiple of the requested samples.
+const MAX_ROUNDS_FACTOR: usize = 4;
+
+/// Upper bound for the number of times the attack steps back to an earlier byte.
+const MAX_BACKTRACKS: usize = 8;
+
+/// Reduces a set of timing samples to a single, outlier-resistant estimate.
+pub type Estimator = fn(&mut [Duration]) -> Duration;
+
+/// Returns the median of `samples`.
+pub fn median(samples: &mut [Duration]) -> Duration {
+    samples.sort_unstable();
+    samples[samples.len() / 2]
+}
+
+/// Returns the mean of `samples` after discarding the lowest and highest quarter.
+pub fn trimmed_mean(samples: &mut [Duration]) -> Duration {
+    samples.sort_unstable();
+    let trim = samples.len() / 4;
+    let kept = &samples[trim..samples.len() - trim];
+    kept.iter().sum::<Duration>() / kept.len() as u32
+}
+
+/// Returns the lower quartile of `samples`.
+///
+/// Scheduling hiccups only ever make responses slower, so this is far less affected by them.
+pub fn lower_quartile(samples: &mut [Duration]) -> Duration {
+    samples.sort_unstable();
+    samples[samples.len() / 4]
+}
+
+/// Recovers the HMAC of `file` from the server at `addr` byte by byte.
+///
+/// Each candidate byte is timed `samples` times and reduced with `estimator`.
+/// The final byte does not leak through timing, so it is found by checking the server's response.
+/// Whenever no candidate stands out, the previous byte is assumed to be wrong and recovered again.
+/// Returns `None` if no valid signature was found within a few such retries,
+/// and an error if a request to the server fails.
+pub fn recover_signature(
+    addr: SocketAddr,
+    file: &str,
+    samples: usize,
+    estimator: Estimator,
+) -> io::Result<Option<[u8; SIGNATURE_LENGTH]>> {
+    let mut signature = [0; SIGNATURE_LENGTH];
+    let mut position = 0;
+    let mut backtracks = 0;
+    loop {
+        if position == SIGNATURE_LENGTH - 1 {
+            if let Some(signature) = recover_last_byte(addr, file, signature)? {
+                return Ok(Some(signature));
+            }
+        } else if let Some(byte) =
+            recover_next_byte(addr, file, &signature, position, samples, estimator)?
+        {
+            signature[position] = byte;
+            position += 1;
+            continue;
+        }
+
+        if backtracks == MAX_BACKTRACKS {
+            return Ok(None);
+        }
+        backtracks += 1;
+        position = position.saturating_sub(1);
+    }
+}
+
+/// Completes `signature` by trying every value of its final byte against the server at `addr`.
+///
+/// Returns `None` if none of them is accepted, i.e. if the rest of `signature` is wrong.
+pub fn recover_last_byte(
+    addr: SocketAddr,
+    file: &str,
+    mut signature: [u8; SIGNATURE_LENGTH],
+) -> io::Result<Option<[u8; SIGNATURE_LENGTH]>> {
+    for byte in 0..=255 {
+        signature[SIGNATURE_LENGTH - 1] = byte;
+        if hmac_timing::request(addr, file, &signature)? {
+            return Ok(Some(signature));
+        }
+    }
+    Ok(None)
+}
+
+/// Finds the byte at `position`, assuming everything before it in `signature` is correct.
+///
+/// Takes at least `samples` rounds of measurements, and keeps sampling (up to four times as many)
+/// while the slowest candidate does not clearly stand out from the runner-up.
+/// Returns `None` if none ever does, which usually means an earlier byte is wrong.
+pub fn recover_next_byte(
+    addr: SocketAddr,
+    file: &str,
+    signature: &[u8; SIGNATURE_LENGTH],
+    position: usize,
+    samples: usize,
+    estimator: Estimator,
+) -> io::Result<Option<u8>> {
+    let mut guess = *signature;
+    let mut timings = vec![Vec::new(); 256];
+    let mut order = (0..=255).collect::<Vec<u8>>();
+    for round in 1..=MAX_ROUNDS_FACTOR * samples {
+        order.shuffle(&mut rand::rng());
+        for &byte in &order {
+            guess[position] = byte;
+            let start = Instant::now();
+            hmac_timing::request(addr, file, &guess)?;
+            timings[byte as usize].push(start.elapsed());
+        }
+        if round >= samples
+            && let Some(byte) = clear_leader(&timings, estimator)
+        {
+            return Ok(Some(byte));
+        }
+    }
+    Ok(None)
+}
+
+/// Returns the slowest candidate, if it is further above the typical candidate than twice the runner-up.
+fn clear_leader(timings: &[Vec<Duration>], estimator: Estimator) -> Option<u8> {
+    let (best, best_estimate) = slowest(timings, estimator);
+    let mut sorted = estimates(timings, estimator);
+    let baseline = median(&mut sorted);
+    let runner_up = sorted[sorted.len() - 2];
+    let lead = best_estimate.saturating_sub(baseline);
+    (lead > 2 * runner_up.saturating_sub(baseline)).then_some(best)
+}
+
+/// Returns the candidate with the highest estimated response time, together with that estimate.
+fn slowest(timings: &[Vec<Duration>], estimator: Estimator) -> (u8, Duration) {
+    let estimates = estimates(timings, estimator);
+    let (byte, estimate) = estimates
+        .into_iter()
+        .enumerate()
+        .max_by_key(|(_, t)| *t)
+        .unwrap();
+    (byte as u8, estimate)
+}
+
+/// Reduces the samples of every candidate to a single estimate.
+fn estimates(timings: &[Vec<Duration>], estimator: Estimator) -> Vec<Duration> {
+    timings
+        .iter()
+        .map(|timings| estimator(&mut timings.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_attacks::hmac_timing::HmacTimingServer;
+    use cryptopals_primitives::hmac::Hmac;
+    use cryptopals_primitives::sha1::Sha1Core;
+
+    use super::*;
+
+    #[test]
+    fn estimators() {
+        let ms = Duration::from_millis;
+        assert_eq!(median(&mut [ms(3), ms(1), ms(100), ms(2), ms(4)]), ms(3));
+        assert_eq!(trimmed_mean(&mut [ms(0), ms(2), ms(4), ms(100)]), ms(3));
+        assert_eq!(lower_quartile(&mut [ms(9), ms(1), ms(100), ms(2)]), ms(2));
+    }
+
+    #[test]
+    fn last_byte() {
+        let server = HmacTimingServer::with_key(b"key", Duration::ZERO).unwrap();
+        let expected = Hmac::<Sha1Core>::mac(b"key", b"foo");
+
+        let mut signature = [0; SIGNATURE_LENGTH];
+        signature[..SIGNATURE_LENGTH - 1].copy_from_slice(&expected[..SIGNATURE_LENGTH - 1]);
+        assert_eq!(
+            recover_last_byte(server.addr(), "foo", signature).unwrap(),
+            Some(expected.into())
+        );
+
+        signature[0] ^= 1;
+        assert_eq!(
+            recover_last_byte(server.addr(), "foo", signature).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn first_bytes() {
+        const KEY: &[u8] = b"YELLOW SUBMARINE";
+        const FILE: &str = "foo";
+        let server = HmacTimingServer::with_key(KEY, Duration::from_millis(5)).unwrap();
+        let expected = Hmac::<Sha1Core>::mac(KEY, FILE.as_bytes());
+
+        let mut signature = [0; SIGNATURE_LENGTH];
+        for i in 0..2 {
+            let byte = recover_next_byte(server.addr(), FILE, &signature, i, 3, median);
+            assert_eq!(byte.unwrap(), Some(expected[i]));
+            signature[i] = expected[i];
+        }
+    }
+
+    /// Recovering the full tag at 5 ms per byte takes a long time and depends on wall-clock timing.
+    #[test]
+    #[ignore = "slow and timing sensitive"]
+    fn challenge() {
+        const KEY: &[u8] = b"YELLOW SUBMARINE";
+        const FILE: &str = "foo";
+        let server = HmacTimingServer::with_key(KEY, Duration::from_millis(5)).unwrap();
+        let expected = Hmac::<Sha1Core>::mac(KEY, FILE.as_bytes());
+
+        assert_eq!(
+            recover_signature(server.addr(), FILE, 3, lower_quartile).unwrap(),
+            Some(expected.into())
+        );
+    }
+}
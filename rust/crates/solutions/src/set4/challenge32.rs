@@ -0,0 +1,50 @@
+//! # Challenge 32
+//!
+//! Solution to [Challenge 32](https://cryptopals.com/sets/4/challenges/32) of Cryptopals.
+//!
+//! The attack from [Challenge 31](super::challenge31) still works for much smaller delays,
+//! it just needs more samples per candidate. Taking the lower quartile of each candidate's samples
+//! keeps the occasional slow response from drowning out the much smaller difference.
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use cryptopals_attacks::hmac_timing::HmacTimingServer;
+    use cryptopals_primitives::hmac::Hmac;
+    use cryptopals_primitives::sha1::Sha1Core;
+
+    use crate::set4::challenge31::{
+        lower_quartile, recover_next_byte, recover_signature, trimmed_mean,
+    };
+
+    #[test]
+    fn first_bytes() {
+        const KEY: &[u8] = b"YELLOW SUBMARINE";
+        const FILE: &str = "foo";
+        let server = HmacTimingServer::with_key(KEY, Duration::from_micros(500)).unwrap();
+        let expected = Hmac::<Sha1Core>::mac(KEY, FILE.as_bytes());
+
+        let mut signature = [0; 20];
+        for i in 0..3 {
+            let byte = recover_next_byte(server.addr(), FILE, &signature, i, 5, trimmed_mean);
+            assert_eq!(byte.unwrap(), Some(expected[i]));
+            signature[i] = expected[i];
+        }
+    }
+
+    /// Recovering the full tag at 500 µs per byte takes a long time and depends on wall-clock timing.
+    #[test]
+    #[ignore = "slow and timing sensitive"]
+    fn challenge() {
+        const KEY: &[u8] = b"YELLOW SUBMARINE";
+        const FILE: &str = "foo";
+        let server = HmacTimingServer::with_key(KEY, Duration::from_micros(500)).unwrap();
+        let expected = Hmac::<Sha1Core>::mac(KEY, FILE.as_bytes());
+
+        assert_eq!(
+            recover_signature(server.addr(), FILE, 15, lower_quartile).unwrap(),
+            Some(expected.into())
+        );
+    }
+}
@@ -0,0 +1,10 @@
+//! # Set 5
+//!
+//! Solutions for [Set 5](https://cryptopals.com/sets/5) of Cryptopals.
+
+pub mod challenge33;
+pub mod challenge34;
+pub mod challenge35;
+pub mod challenge36;
+pub mod challenge37;
+pub mod challenge38;
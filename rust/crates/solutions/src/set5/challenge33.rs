@@ -0,0 +1,38 @@
+//! # Challenge 33
+//!
+//! Solution to [Challenge 33](https://cryptopals.com/sets/5/challenges/33) of Cryptopals.
+
+use cryptopals_bigint::dh::{Group, Kdf};
+
+/// Runs a Diffie-Hellman key exchange in `group` between two freshly generated parties.
+///
+/// Returns the AES keys both sides derived, which should be identical.
+pub fn key_exchange(group: &Group) -> ([u8; 16], [u8; 16]) {
+    let mut rng = rand::rng();
+    let alice = group.generate_key_pair(&mut rng);
+    let bob = group.generate_key_pair(&mut rng);
+
+    let s_alice = alice.shared_secret(bob.public());
+    let s_bob = bob.shared_secret(alice.public());
+    (
+        Kdf::Sha256.derive_key(&s_alice),
+        Kdf::Sha256.derive_key(&s_bob),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toy_group() {
+        let (a, b) = key_exchange(&Group::toy());
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn challenge() {
+        let (a, b) = key_exchange(&Group::modp_1536());
+        assert_eq!(a, b);
+    }
+}
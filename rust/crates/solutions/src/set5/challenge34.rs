@@ -0,0 +1,59 @@
+//! # Challenge 34
+//!
+//! Solution to [Challenge 34](https://cryptopals.com/sets/5/challenges/34) of Cryptopals.
+
+use cryptopals_attacks::dh_protocol::{Direction, Mallory, Message};
+use cryptopals_bigint::BigUint;
+use cryptopals_bigint::dh::Kdf;
+
+/// Man-in-the-middle replacing both public keys with `p`.
+///
+/// Both parties then compute the shared secret `p^x mod p = 0`,
+/// so Mallory knows the key without ever learning a private exponent.
+#[derive(Clone, Debug, Default)]
+pub struct ParameterInjection {
+    p: Option<BigUint>,
+    /// Plaintexts of all encrypted messages relayed so far.
+    pub recovered: Vec<Vec<u8>>,
+}
+
+impl Mallory for ParameterInjection {
+    fn intercept(&mut self, _direction: Direction, message: Message) -> Message {
+        match message {
+            Message::Negotiate(ref group) => {
+                self.p = Some(group.p.clone());
+                message
+            }
+            Message::PublicKey(_) => {
+                Message::PublicKey(self.p.clone().expect("group not negotiated"))
+            }
+            Message::Encrypted { .. } => {
+                let key = Kdf::Sha1.derive_key(&BigUint::zero());
+                if let Ok(plaintext) = message.decrypt(&key) {
+                    self.recovered.push(plaintext);
+                }
+                message
+            }
+            message => message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_attacks::dh_protocol::run_protocol;
+    use cryptopals_bigint::dh::Group;
+
+    use super::*;
+
+    #[test]
+    fn challenge() {
+        let message = b"Attack at dawn!";
+        let mut mallory = ParameterInjection::default();
+        let echo = run_protocol(&Group::modp_1536(), message, &mut mallory).unwrap();
+
+        // neither Alice nor Bob notice anything
+        assert_eq!(echo, message);
+        assert_eq!(mallory.recovered, [message, message]);
+    }
+}
@@ -0,0 +1,127 @@
+//! # Challenge 35
+//!
+//! Solution to [Challenge 35](https://cryptopals.com/sets/5/challenges/35) of Cryptopals.
+
+use cryptopals_attacks::dh_protocol::{Direction, Mallory, Message};
+use cryptopals_bigint::BigUint;
+use cryptopals_bigint::dh::{Group, Kdf};
+
+/// Degenerate generator substituted during group negotiation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MaliciousGenerator {
+    /// `g = 1`, so every power is 1.
+    One,
+    /// `g = p`, so every power is 0.
+    P,
+    /// `g = p - 1`, so every power is either 1 or `p - 1`.
+    PMinusOne,
+}
+
+impl MaliciousGenerator {
+    fn generator(self, p: &BigUint) -> BigUint {
+        match self {
+            Self::One => BigUint::one(),
+            Self::P => p.clone(),
+            Self::PMinusOne => p - &BigUint::one(),
+        }
+    }
+}
+
+/// Man-in-the-middle replacing the negotiated generator.
+///
+/// The shared secret then only takes one or two values, and Mallory learns which one from the public keys.
+#[derive(Clone, Debug)]
+pub struct GeneratorInjection {
+    generator: MaliciousGenerator,
+    group: Option<Group>,
+    public_keys: Vec<BigUint>,
+    /// Plaintexts of all encrypted messages relayed so far.
+    pub recovered: Vec<Vec<u8>>,
+}
+
+impl GeneratorInjection {
+    pub fn new(generator: MaliciousGenerator) -> Self {
+        Self {
+            generator,
+            group: None,
+            public_keys: Vec::new(),
+            recovered: Vec::new(),
+        }
+    }
+
+    /// Deduces the shared secret from the public keys.
+    ///
+    /// With `g = p - 1` the secret `g^(ab)` is 1 if either exponent is even,
+    /// which shows up as a public key of 1, and `p - 1` otherwise.
+    fn shared_secret(&self) -> BigUint {
+        let group = self.group.as_ref().expect("group not negotiated");
+        match self.generator {
+            MaliciousGenerator::One => BigUint::one(),
+            MaliciousGenerator::P => BigUint::zero(),
+            MaliciousGenerator::PMinusOne if self.public_keys.contains(&BigUint::one()) => {
+                BigUint::one()
+            }
+            MaliciousGenerator::PMinusOne => group.g.clone(),
+        }
+    }
+}
+
+impl Mallory for GeneratorInjection {
+    fn intercept(&mut self, _direction: Direction, message: Message) -> Message {
+        match message {
+            // Bob accepts and acknowledges the malicious group, so Alice adopts it as well
+            Message::Negotiate(group) => {
+                let g = self.generator.generator(&group.p);
+                let group = Group::new(group.p, g);
+                self.group = Some(group.clone());
+                Message::Negotiate(group)
+            }
+            Message::PublicKey(ref public) => {
+                self.public_keys.push(public.clone());
+                message
+            }
+            Message::Encrypted { .. } => {
+                let key = Kdf::Sha1.derive_key(&self.shared_secret());
+                if let Ok(plaintext) = message.decrypt(&key) {
+                    self.recovered.push(plaintext);
+                }
+                message
+            }
+            message => message,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_attacks::dh_protocol::run_protocol;
+
+    use super::*;
+
+    fn attack(generator: MaliciousGenerator) {
+        let message = b"Attack at dawn!";
+        let mut mallory = GeneratorInjection::new(generator);
+        let echo = run_protocol(&Group::modp_1536(), message, &mut mallory).unwrap();
+
+        assert_eq!(echo, message);
+        assert_eq!(mallory.recovered, [message, message]);
+    }
+
+    #[test]
+    fn generator_one() {
+        attack(MaliciousGenerator::One);
+    }
+
+    #[test]
+    fn generator_p() {
+        attack(MaliciousGenerator::P);
+    }
+
+    #[test]
+    fn generator_p_minus_one() {
+        // the secret depends on the parity of both private keys, so cover several exchanges
+        for _ in 0..8 {
+            attack(MaliciousGenerator::PMinusOne);
+        }
+    }
+}
@@ -0,0 +1,26 @@
+//! # Challenge 36
+//!
+//! Solution to [Challenge 36](https://cryptopals.com/sets/5/challenges/36) of Cryptopals.
+//!
+//! Client and server are implemented in [`cryptopals_attacks::srp`],
+//! which the following challenges attack.
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_attacks::srp::{Server, Variant, login};
+    use cryptopals_bigint::dh::Group;
+
+    #[test]
+    fn challenge() {
+        let group = Group::modp_1536();
+        let mut server = Server::new(group.clone(), Variant::Srp6a);
+        server.register("alice@example.com", "correct horse battery staple");
+
+        let attempt = |password: &str| {
+            server
+                .connect(|conn| login(&conn, &group, Variant::Srp6a, "alice@example.com", password))
+        };
+        assert!(attempt("correct horse battery staple").unwrap());
+        assert!(!attempt("Tr0ub4dor&3").unwrap());
+    }
+}
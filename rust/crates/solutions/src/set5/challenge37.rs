@@ -0,0 +1,54 @@
+//! # Challenge 37
+//!
+//! Solution to [Challenge 37](https://cryptopals.com/sets/5/challenges/37) of Cryptopals.
+
+use cryptopals_attacks::srp::{Connection, Message, SrpError, proof, session_key};
+use cryptopals_bigint::BigUint;
+use cryptopals_bigint::dh::Group;
+
+/// Logs in as `email` without knowing the password, by sending `A = multiple * N`.
+///
+/// The server then computes `S = (A * v^u)^b = 0 mod N`, so the session key is `SHA256(0)`.
+/// Returns whether the server accepted the login.
+pub fn zero_key_login(
+    connection: &Connection,
+    group: &Group,
+    email: &str,
+    multiple: u64,
+) -> Result<bool, SrpError> {
+    connection.send(Message::Hello {
+        email: email.to_string(),
+        public: &group.p * &BigUint::from(multiple),
+    })?;
+    let salt = match connection.receive()? {
+        Message::Challenge { salt, .. } => salt,
+        message => return Err(SrpError::UnexpectedMessage(message)),
+    };
+
+    let key = session_key(&BigUint::zero());
+    connection.send(Message::Proof(proof(&key, &salt)))?;
+    match connection.receive()? {
+        Message::Result(valid) => Ok(valid),
+        message => Err(SrpError::UnexpectedMessage(message)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cryptopals_attacks::srp::{Server, Variant};
+
+    use super::*;
+
+    #[test]
+    fn challenge() {
+        let group = Group::modp_1536();
+        let mut server = Server::new(group.clone(), Variant::Srp6a);
+        server.register("alice@example.com", "a password nobody will ever guess");
+
+        for multiple in [0, 1, 2] {
+            let accepted =
+                server.connect(|conn| zero_key_login(&conn, &group, "alice@example.com", multiple));
+            assert!(accepted.unwrap());
+        }
+    }
+}
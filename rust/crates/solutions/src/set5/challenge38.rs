@@ -0,0 +1,99 @@
+//! # Challenge 38
+//!
+//! Solution to [Challenge 38](https://cryptopals.com/sets/5/challenges/38) of Cryptopals.
+
+use cryptopals_attacks::srp::{Connection, Message, SrpError, private_key, proof, session_key};
+use cryptopals_bigint::BigUint;
+use cryptopals_bigint::dh::Group;
+
+/// Login attempt captured while posing as a simplified SRP server.
+#[derive(Clone, Debug)]
+pub struct Capture {
+    pub salt: Vec<u8>,
+    pub a: BigUint,
+    pub proof: Vec<u8>,
+}
+
+/// Poses as a simplified SRP server and records the client's login attempt.
+///
+/// Chooses an empty salt, `b = 1` and `u = 1`, so that the client's secret is just `S = A * v mod N`.
+pub fn impersonate_server(connection: &Connection, group: &Group) -> Result<Capture, SrpError> {
+    let a = match connection.receive()? {
+        Message::Hello { public, .. } => public,
+        message => return Err(SrpError::UnexpectedMessage(message)),
+    };
+    let salt = Vec::new();
+    connection.send(Message::SimplifiedChallenge {
+        salt: salt.clone(),
+        public: group.g.clone(),
+        u: BigUint::one(),
+    })?;
+    let proof = match connection.receive()? {
+        Message::Proof(proof) => proof,
+        message => return Err(SrpError::UnexpectedMessage(message)),
+    };
+    // accept, so the client does not notice anything
+    connection.send(Message::Result(true))?;
+    Ok(Capture { salt, a, proof })
+}
+
+/// Runs an offline dictionary attack on a captured login attempt.
+///
+/// Returns the first password in `wordlist` that reproduces the client's proof.
+pub fn crack_password<'a>(
+    capture: &Capture,
+    group: &Group,
+    wordlist: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    let n = &group.p;
+    wordlist.into_iter().find(|password| {
+        let x = private_key(&capture.salt, password);
+        let v = group.g.modpow(&x, n);
+        let s = &(&capture.a * &v) % n;
+        proof(&session_key(&s), &capture.salt) == capture.proof
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use cryptopals_attacks::srp::{Variant, login};
+    use rand::prelude::*;
+
+    use super::*;
+
+    const WORDLIST: &str = include_str!("../../../../../data/set5/38.txt");
+
+    #[test]
+    fn challenge() {
+        let group = Group::modp_1536();
+        let password = *WORDLIST
+            .lines()
+            .collect::<Vec<_>>()
+            .choose(&mut rand::rng())
+            .unwrap();
+
+        let (client, mallory) = Connection::pair();
+        let capture = thread::scope(|s| {
+            let group = &group;
+            let victim = s.spawn(move || {
+                login(
+                    &client,
+                    group,
+                    Variant::Simplified,
+                    "alice@example.com",
+                    password,
+                )
+            });
+            let capture = impersonate_server(&mallory, group).unwrap();
+            assert!(victim.join().unwrap().unwrap());
+            capture
+        });
+
+        assert_eq!(
+            crack_password(&capture, &group, WORDLIST.lines()),
+            Some(password)
+        );
+    }
+}